@@ -1,10 +1,26 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 from .ormsgpack import (
+    OPT_BIG_INT_AS_EXT,
+    OPT_BIN_AS_BYTEARRAY,
+    OPT_BIN_AS_MEMORYVIEW,
     OPT_DATETIME_AS_TIMESTAMP_EXT,
+    OPT_DATETIME_AS_TZ_EXT,
+    OPT_DECIMAL_AS_EXT,
+    OPT_ENUM_BY_NAME,
+    OPT_ENUM_BY_QUALIFIED_NAME,
+    OPT_EXT_AS_MEMORYVIEW,
+    OPT_INVALID_STR_AS_BYTES,
+    OPT_INVALID_STR_REPLACE,
+    OPT_INVALID_STR_SURROGATEESCAPE,
     OPT_NAIVE_UTC,
+    OPT_NAMEDTUPLE_AS_MAP,
     OPT_NON_STR_KEYS,
+    OPT_NUMPY_AS_EXT,
+    OPT_OMIT_DEFAULTS,
     OPT_OMIT_MICROSECONDS,
+    OPT_OMIT_NONE,
+    OPT_PASSTHROUGH_ATTRS,
     OPT_PASSTHROUGH_BIG_INT,
     OPT_PASSTHROUGH_DATACLASS,
     OPT_PASSTHROUGH_DATETIME,
@@ -12,15 +28,30 @@ from .ormsgpack import (
     OPT_PASSTHROUGH_SUBCLASS,
     OPT_PASSTHROUGH_TUPLE,
     OPT_PASSTHROUGH_UUID,
+    OPT_PYDANTIC_BY_ALIAS,
+    OPT_PYDANTIC_COMPUTED_FIELDS,
+    OPT_PYDANTIC_EXCLUDE,
     OPT_REPLACE_SURROGATES,
+    OPT_SERIALIZE_COLLECTIONS,
+    OPT_SERIALIZE_DECIMAL,
+    OPT_SERIALIZE_ITERABLES,
     OPT_SERIALIZE_NUMPY,
     OPT_SERIALIZE_PYDANTIC,
+    OPT_SERIALIZE_SETS,
+    OPT_SERIALIZE_TIMEDELTA,
     OPT_SORT_KEYS,
+    OPT_SORT_SETS,
+    OPT_STRUCT_AS_ARRAY,
+    OPT_TIMEDELTA_AS_EXT,
+    OPT_TIMEDELTA_AS_MICROSECONDS,
+    OPT_TIMEDELTA_AS_SECONDS,
+    OPT_TIMESTAMP_EXT_AS_TIMESTAMP,
     OPT_UTC_Z,
     Ext,
     Fragment,
     MsgpackDecodeError,
     MsgpackEncodeError,
+    Timestamp,
     __version__,
     packb,
     unpackb,
@@ -34,10 +65,27 @@ __all__ = (
     "Fragment",
     "MsgpackDecodeError",
     "MsgpackEncodeError",
+    "Timestamp",
+    "OPT_BIG_INT_AS_EXT",
+    "OPT_BIN_AS_BYTEARRAY",
+    "OPT_BIN_AS_MEMORYVIEW",
     "OPT_DATETIME_AS_TIMESTAMP_EXT",
+    "OPT_DATETIME_AS_TZ_EXT",
+    "OPT_DECIMAL_AS_EXT",
+    "OPT_ENUM_BY_NAME",
+    "OPT_ENUM_BY_QUALIFIED_NAME",
+    "OPT_EXT_AS_MEMORYVIEW",
+    "OPT_INVALID_STR_AS_BYTES",
+    "OPT_INVALID_STR_REPLACE",
+    "OPT_INVALID_STR_SURROGATEESCAPE",
     "OPT_NAIVE_UTC",
+    "OPT_NAMEDTUPLE_AS_MAP",
     "OPT_NON_STR_KEYS",
+    "OPT_NUMPY_AS_EXT",
+    "OPT_OMIT_DEFAULTS",
     "OPT_OMIT_MICROSECONDS",
+    "OPT_OMIT_NONE",
+    "OPT_PASSTHROUGH_ATTRS",
     "OPT_PASSTHROUGH_BIG_INT",
     "OPT_PASSTHROUGH_DATACLASS",
     "OPT_PASSTHROUGH_DATETIME",
@@ -45,9 +93,23 @@ __all__ = (
     "OPT_PASSTHROUGH_SUBCLASS",
     "OPT_PASSTHROUGH_TUPLE",
     "OPT_PASSTHROUGH_UUID",
+    "OPT_PYDANTIC_BY_ALIAS",
+    "OPT_PYDANTIC_COMPUTED_FIELDS",
+    "OPT_PYDANTIC_EXCLUDE",
     "OPT_REPLACE_SURROGATES",
+    "OPT_SERIALIZE_COLLECTIONS",
+    "OPT_SERIALIZE_DECIMAL",
+    "OPT_SERIALIZE_ITERABLES",
     "OPT_SERIALIZE_NUMPY",
     "OPT_SERIALIZE_PYDANTIC",
+    "OPT_SERIALIZE_SETS",
+    "OPT_SERIALIZE_TIMEDELTA",
     "OPT_SORT_KEYS",
+    "OPT_SORT_SETS",
+    "OPT_STRUCT_AS_ARRAY",
+    "OPT_TIMEDELTA_AS_EXT",
+    "OPT_TIMEDELTA_AS_MICROSECONDS",
+    "OPT_TIMEDELTA_AS_SECONDS",
+    "OPT_TIMESTAMP_EXT_AS_TIMESTAMP",
     "OPT_UTC_Z",
 )
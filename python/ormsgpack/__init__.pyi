@@ -1,3 +1,4 @@
+import datetime
 from collections.abc import Callable
 from typing import Any
 
@@ -6,8 +7,17 @@ __version__: str
 def packb(
     obj: Any,
     /,
-    default: Callable[[Any], Any] | None = ...,
+    default: Callable[[Any], Any]
+    | dict[type[Any], Callable[[Any], Any] | None]
+    | None = ...,
     option: int | None = None,
+    *,
+    ext_types: dict[type[Any], tuple[int, Callable[[Any], bytes]]] | None = ...,
+    big_int_tag: int = ...,
+    datetime_tag: int = ...,
+    decimal_tag: int = ...,
+    numpy_tag: int = ...,
+    timedelta_tag: int = ...,
 ) -> bytes: ...
 def unpackb(
     obj: bytes | bytearray | memoryview,
@@ -15,6 +25,13 @@ def unpackb(
     *,
     ext_hook: Callable[[int, bytes], Any] | None = ...,
     option: int | None = ...,
+    tzinfo: datetime.tzinfo | None = ...,
+    type: type[Any] | None = ...,
+    big_int_tag: int = ...,
+    datetime_tag: int = ...,
+    decimal_tag: int = ...,
+    numpy_tag: int = ...,
+    timedelta_tag: int = ...,
 ) -> Any: ...
 
 class MsgpackDecodeError(ValueError): ...
@@ -26,9 +43,37 @@ class Ext:
 class Fragment:
     def __init__(self, data: bytes) -> None: ...
 
+class Timestamp:
+    def __init__(self, seconds: int, nanoseconds: int = 0) -> None: ...
+    @property
+    def seconds(self) -> int: ...
+    @property
+    def nanoseconds(self) -> int: ...
+    def to_datetime(self) -> datetime.datetime: ...
+    @classmethod
+    def from_datetime(cls, dt: datetime.datetime, /) -> Timestamp: ...
+    def __lt__(self, other: Timestamp) -> bool: ...
+    def __le__(self, other: Timestamp) -> bool: ...
+    def __gt__(self, other: Timestamp) -> bool: ...
+    def __ge__(self, other: Timestamp) -> bool: ...
+
+OPT_BIG_INT_AS_EXT: int
+OPT_BIN_AS_BYTEARRAY: int
+OPT_BIN_AS_MEMORYVIEW: int
 OPT_DATETIME_AS_TIMESTAMP_EXT: int
+OPT_DATETIME_AS_TZ_EXT: int
+OPT_DECIMAL_AS_EXT: int
+OPT_ENUM_BY_NAME: int
+OPT_ENUM_BY_QUALIFIED_NAME: int
+OPT_EXT_AS_MEMORYVIEW: int
+OPT_INVALID_STR_AS_BYTES: int
+OPT_INVALID_STR_REPLACE: int
+OPT_INVALID_STR_SURROGATEESCAPE: int
 OPT_NAIVE_UTC: int
+OPT_OMIT_DEFAULTS: int
 OPT_OMIT_MICROSECONDS: int
+OPT_OMIT_NONE: int
+OPT_PASSTHROUGH_ATTRS: int
 OPT_PASSTHROUGH_BIG_INT: int
 OPT_PASSTHROUGH_DATACLASS: int
 OPT_PASSTHROUGH_DATETIME: int
@@ -36,9 +81,25 @@ OPT_PASSTHROUGH_ENUM: int
 OPT_PASSTHROUGH_SUBCLASS: int
 OPT_PASSTHROUGH_TUPLE: int
 OPT_PASSTHROUGH_UUID: int
+OPT_PYDANTIC_BY_ALIAS: int
+OPT_PYDANTIC_COMPUTED_FIELDS: int
+OPT_PYDANTIC_EXCLUDE: int
 OPT_REPLACE_SURROGATES: int
+OPT_SERIALIZE_COLLECTIONS: int
+OPT_SERIALIZE_DECIMAL: int
+OPT_SERIALIZE_ITERABLES: int
 OPT_SERIALIZE_NUMPY: int
 OPT_SERIALIZE_PYDANTIC: int
+OPT_SERIALIZE_SETS: int
+OPT_SERIALIZE_TIMEDELTA: int
+OPT_NAMEDTUPLE_AS_MAP: int
 OPT_NON_STR_KEYS: int
+OPT_NUMPY_AS_EXT: int
 OPT_SORT_KEYS: int
+OPT_SORT_SETS: int
+OPT_STRUCT_AS_ARRAY: int
+OPT_TIMEDELTA_AS_EXT: int
+OPT_TIMEDELTA_AS_MICROSECONDS: int
+OPT_TIMEDELTA_AS_SECONDS: int
+OPT_TIMESTAMP_EXT_AS_TIMESTAMP: int
 OPT_UTC_Z: int
@@ -4,12 +4,13 @@ use crate::deserialize::DeserializeError;
 use crate::exc::*;
 use crate::ffi::*;
 use crate::io::Read;
-use crate::msgpack::{read_timestamp, Marker};
+use crate::msgpack::{read_timestamp, read_timestamp_value, Marker};
 use crate::opt::*;
 use crate::state::State;
+use crate::timestamp::timestamp_from_value;
 use crate::util::unlikely;
 use chrono::{Datelike, Timelike};
-use simdutf8::basic::{from_utf8, Utf8Error};
+use simdutf8::basic::from_utf8;
 use std::borrow::Cow;
 use std::os::raw::c_char;
 use std::ptr::NonNull;
@@ -17,12 +18,17 @@ use std::ptr::NonNull;
 const RECURSION_LIMIT: u16 = 1024;
 
 fn deserialize_slice(
+    ptr: *mut pyo3::ffi::PyObject,
     contents: &[u8],
     state: *mut State,
     ext_hook: Option<NonNull<pyo3::ffi::PyObject>>,
+    tzinfo: Option<NonNull<pyo3::ffi::PyObject>>,
     opts: Opt,
+    ext_tags: ExtTags,
 ) -> Result<NonNull<pyo3::ffi::PyObject>, DeserializeError<'static>> {
-    let mut deserializer = Deserializer::new(contents, state, ext_hook, opts);
+    let source = Source::new(ptr, contents);
+    let mut deserializer =
+        Deserializer::new(contents, source, state, ext_hook, tzinfo, opts, ext_tags);
     deserializer
         .deserialize()
         .map_err(|e| DeserializeError::new(Cow::Owned(e.to_string())))
@@ -32,17 +38,39 @@ pub fn deserialize(
     ptr: *mut pyo3::ffi::PyObject,
     state: *mut State,
     ext_hook: Option<NonNull<pyo3::ffi::PyObject>>,
+    tzinfo: Option<NonNull<pyo3::ffi::PyObject>>,
     opts: Opt,
+    ext_tags: ExtTags,
 ) -> Result<NonNull<pyo3::ffi::PyObject>, DeserializeError<'static>> {
+    if unlikely(opts & BIN_AS_BYTEARRAY != 0 && opts & BIN_AS_MEMORYVIEW != 0) {
+        return Err(DeserializeError::new(Cow::Borrowed(
+            "OPT_BIN_AS_BYTEARRAY is not compatible with OPT_BIN_AS_MEMORYVIEW",
+        )));
+    }
+
+    if unlikely(opts & NAIVE_UTC != 0 && tzinfo.is_some()) {
+        return Err(DeserializeError::new(Cow::Borrowed(
+            "OPT_NAIVE_UTC is not compatible with tzinfo",
+        )));
+    }
+
+    let invalid_str_opts =
+        opts & (INVALID_STR_AS_BYTES | INVALID_STR_REPLACE | INVALID_STR_SURROGATEESCAPE);
+    if unlikely(invalid_str_opts & invalid_str_opts.wrapping_sub(1) != 0) {
+        return Err(DeserializeError::new(Cow::Borrowed(
+            "OPT_INVALID_STR_AS_BYTES, OPT_INVALID_STR_REPLACE and OPT_INVALID_STR_SURROGATEESCAPE are mutually exclusive",
+        )));
+    }
+
     let obj_type_ptr = ob_type!(ptr);
 
     if obj_type_ptr == &raw mut pyo3::ffi::PyBytes_Type {
         let contents = unsafe { pybytes_as_bytes(ptr) };
-        deserialize_slice(contents, state, ext_hook, opts)
+        deserialize_slice(ptr, contents, state, ext_hook, tzinfo, opts, ext_tags)
     } else if obj_type_ptr == &raw mut pyo3::ffi::PyMemoryView_Type {
         if let Some(buffer) = unsafe { Buffer::get(ptr) } {
             let contents = buffer.as_bytes();
-            deserialize_slice(contents, state, ext_hook, opts)
+            deserialize_slice(ptr, contents, state, ext_hook, tzinfo, opts, ext_tags)
         } else {
             Err(DeserializeError::new(Cow::Borrowed(
                 "Input type memoryview must be a C contiguous buffer",
@@ -50,7 +78,7 @@ pub fn deserialize(
         }
     } else if obj_type_ptr == &raw mut pyo3::ffi::PyByteArray_Type {
         let contents = unsafe { pybytearray_as_bytes(ptr) };
-        deserialize_slice(contents, state, ext_hook, opts)
+        deserialize_slice(ptr, contents, state, ext_hook, tzinfo, opts, ext_tags)
     } else {
         Err(DeserializeError::new(Cow::Borrowed(
             "Input must be bytes, bytearray, memoryview",
@@ -58,6 +86,112 @@ pub fn deserialize(
     }
 }
 
+/// The object being deserialized, from which memoryview slices are created
+/// when binary objects are not copied.
+struct Source {
+    ptr: *mut pyo3::ffi::PyObject,
+    data: *const u8,
+    view: *mut pyo3::ffi::PyObject,
+}
+
+impl Source {
+    fn new(ptr: *mut pyo3::ffi::PyObject, contents: &[u8]) -> Self {
+        Source {
+            ptr: ptr,
+            data: contents.as_ptr(),
+            view: std::ptr::null_mut(),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    unsafe fn create_view(&mut self) -> bool {
+        let view = pyo3::ffi::PyMemoryView_FromObject(self.ptr);
+        if view.is_null() || ob_type!(self.ptr) == &raw mut pyo3::ffi::PyBytes_Type {
+            self.view = view;
+        } else {
+            // bytearray and memoryview objects may be writable and memoryview
+            // objects may have an item size other than 1
+            let cast = pyo3::ffi::PyObject_CallMethod(
+                view,
+                c"cast".as_ptr(),
+                c"s".as_ptr(),
+                c"B".as_ptr(),
+            );
+            pyo3::ffi::Py_DECREF(view);
+            if !cast.is_null() {
+                self.view =
+                    pyo3::ffi::PyObject_CallMethod(cast, c"toreadonly".as_ptr(), std::ptr::null());
+                pyo3::ffi::Py_DECREF(cast);
+            }
+        }
+        if unlikely(self.view.is_null()) {
+            pyo3::ffi::PyErr_Clear();
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Return a read-only memoryview of `value`, which must be a slice of the
+    /// contents of the object.
+    fn slice(&mut self, value: &[u8]) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        unsafe {
+            if self.view.is_null() && !self.create_view() {
+                return Err(Error::Internal);
+            }
+            let start = value.as_ptr().offset_from(self.data);
+            let end = start + value.len() as isize;
+            let ptr = pyo3::ffi::PySequence_GetSlice(self.view, start, end);
+            if unlikely(ptr.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::Internal);
+            }
+            Ok(NonNull::new_unchecked(ptr))
+        }
+    }
+}
+
+impl Drop for Source {
+    fn drop(&mut self) {
+        if !self.view.is_null() {
+            unsafe { pyo3::ffi::Py_DECREF(self.view) };
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn deserialize_invalid_str(data: &[u8], opts: Opt) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+    let errors = if opts & INVALID_STR_REPLACE != 0 {
+        c"replace"
+    } else if opts & INVALID_STR_SURROGATEESCAPE != 0 {
+        c"surrogateescape"
+    } else if opts & INVALID_STR_AS_BYTES != 0 {
+        let ptr = unsafe {
+            pyo3::ffi::PyBytes_FromStringAndSize(
+                data.as_ptr().cast::<c_char>(),
+                data.len() as pyo3::ffi::Py_ssize_t,
+            )
+        };
+        return Ok(unsafe { NonNull::new_unchecked(ptr) });
+    } else {
+        return Err(Error::InvalidStr);
+    };
+    let ptr = unsafe {
+        pyo3::ffi::PyUnicode_DecodeUTF8(
+            data.as_ptr().cast::<c_char>(),
+            data.len() as pyo3::ffi::Py_ssize_t,
+            errors.as_ptr(),
+        )
+    };
+    if unlikely(ptr.is_null()) {
+        unsafe { pyo3::ffi::PyErr_Clear() };
+        return Err(Error::InvalidStr);
+    }
+    Ok(unsafe { NonNull::new_unchecked(ptr) })
+}
+
 #[derive(Debug)]
 enum Error {
     ExtHookFailed,
@@ -66,6 +200,7 @@ enum Error {
     InvalidStr,
     InvalidType(Marker),
     InvalidValue,
+    NumpyMissing,
     RecursionLimitReached,
     UnexpectedEof,
 }
@@ -82,6 +217,7 @@ impl std::fmt::Display for Error {
                 write!(f, "invalid type {marker:?}")
             }
             Error::InvalidValue => f.write_str("invalid value"),
+            Error::NumpyMissing => f.write_str("numpy is not available"),
             Error::RecursionLimitReached => f.write_str(RECURSION_LIMIT_REACHED),
             Error::UnexpectedEof => write!(f, "unexpected end of file"),
         }
@@ -98,18 +234,38 @@ impl From<std::io::Error> for Error {
     }
 }
 
-impl From<Utf8Error> for Error {
-    #[cold]
-    fn from(_: Utf8Error) -> Error {
-        Error::InvalidStr
+/// Split the value of an extension object serialized by `OPT_NUMPY_AS_EXT`
+/// into the dtype descriptor, the shape and the items, or return `None` if
+/// it is malformed. Only the descriptors of the supported datatypes are
+/// accepted, so that no object array can be constructed.
+fn parse_numpy_ext(data: &[u8]) -> Option<(&[u8], impl ExactSizeIterator<Item = u64> + '_, &[u8])> {
+    let (&descr_len, data) = data.split_first()?;
+    let descr = data.get(..descr_len as usize)?;
+    let data = &data[descr_len as usize..];
+    match descr {
+        [b'<' | b'|', b'b' | b'c' | b'f' | b'i' | b'm' | b'u' | b'M' | b'S' | b'U', rest @ ..]
+            if rest
+                .iter()
+                .all(|&b| b.is_ascii_alphanumeric() || b == b'[' || b == b']') => {}
+        _ => return None,
     }
+    let (&num_dimensions, data) = data.split_first()?;
+    let shape = data.get(..num_dimensions as usize * 8)?;
+    let items = &data[shape.len()..];
+    let shape = shape
+        .chunks_exact(8)
+        .map(|each| u64::from_be_bytes(each.try_into().unwrap()));
+    Some((descr, shape, items))
 }
 
 struct Deserializer<R> {
     data: R,
+    source: Source,
     state: *mut State,
     ext_hook: Option<NonNull<pyo3::ffi::PyObject>>,
+    tzinfo: Option<NonNull<pyo3::ffi::PyObject>>,
     opts: Opt,
+    ext_tags: ExtTags,
     recursion: u16,
 }
 
@@ -119,15 +275,21 @@ where
 {
     fn new(
         data: R,
+        source: Source,
         state: *mut State,
         ext_hook: Option<NonNull<pyo3::ffi::PyObject>>,
+        tzinfo: Option<NonNull<pyo3::ffi::PyObject>>,
         opts: Opt,
+        ext_tags: ExtTags,
     ) -> Self {
         Deserializer {
             data: data,
+            source: source,
             state: state,
             ext_hook: ext_hook,
+            tzinfo: tzinfo,
             opts: opts,
+            ext_tags: ext_tags,
             recursion: 0,
         }
     }
@@ -144,38 +306,284 @@ where
     ) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let datetime = read_timestamp(&mut self.data, len)?;
         unsafe {
-            let obj = {
-                let datetime_api = *pyo3::ffi::PyDateTimeAPI();
+            let datetime_api = *pyo3::ffi::PyDateTimeAPI();
+            let tzinfo = match self.tzinfo {
+                Some(tzinfo) => tzinfo.as_ptr(),
+                None if self.opts & NAIVE_UTC != 0 => pyo3::ffi::Py_None(),
+                None => datetime_api.TimeZone_UTC,
+            };
+            let mut obj = (datetime_api.DateTime_FromDateAndTime)(
+                datetime.year(),
+                datetime.month() as i32,
+                datetime.day() as i32,
+                datetime.hour() as i32,
+                datetime.minute() as i32,
+                datetime.second() as i32,
+                (datetime.nanosecond() / 1000) as i32,
+                tzinfo,
+                datetime_api.DateTimeType,
+            );
+            if !obj.is_null() && self.tzinfo.is_some() {
+                let utc = obj;
+                obj = pyobject_call_method_one_arg(tzinfo, (*self.state).fromutc_str, utc);
+                pyo3::ffi::Py_DECREF(utc);
+            }
+            if unlikely(obj.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
+            Ok(NonNull::new_unchecked(obj))
+        }
+    }
+
+    fn deserialize_timestamp_object_ext(
+        &mut self,
+        len: u32,
+    ) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let (seconds, nanoseconds) = read_timestamp_value(&mut self.data, len)?;
+        if unlikely(nanoseconds >= 1_000_000_000) {
+            return Err(Error::InvalidValue);
+        }
+        let obj =
+            unsafe { timestamp_from_value((*self.state).timestamp_type, seconds, nanoseconds) };
+        if unlikely(obj.is_null()) {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(Error::Internal);
+        }
+        Ok(unsafe { NonNull::new_unchecked(obj) })
+    }
+
+    fn deserialize_tz_datetime_ext(
+        &mut self,
+        len: u32,
+    ) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let data = self.data.read_slice(len as usize)?;
+        if unlikely(data.len() < 16) {
+            return Err(Error::InvalidValue);
+        }
+        let seconds = i64::from_be_bytes(data[0..8].try_into().unwrap());
+        let nanoseconds = u32::from_be_bytes(data[8..12].try_into().unwrap());
+        let offset = i32::from_be_bytes(data[12..16].try_into().unwrap());
+        let key = from_utf8(&data[16..]).map_err(|_| Error::InvalidValue)?;
+        if unlikely(nanoseconds >= 1_000_000_000 || offset.unsigned_abs() >= 86400) {
+            return Err(Error::InvalidValue);
+        }
+        let datetime = chrono::DateTime::<chrono::Utc>::from_timestamp(seconds, nanoseconds)
+            .ok_or(Error::InvalidValue)?
+            .naive_utc();
+        unsafe {
+            let datetime_api = *pyo3::ffi::PyDateTimeAPI();
+            let new_datetime = |dt: chrono::NaiveDateTime, tzinfo: *mut pyo3::ffi::PyObject| {
                 (datetime_api.DateTime_FromDateAndTime)(
-                    datetime.year(),
-                    datetime.month() as i32,
-                    datetime.day() as i32,
-                    datetime.hour() as i32,
-                    datetime.minute() as i32,
-                    datetime.second() as i32,
-                    (datetime.nanosecond() / 1000) as i32,
-                    datetime_api.TimeZone_UTC,
+                    dt.year(),
+                    dt.month() as i32,
+                    dt.day() as i32,
+                    dt.hour() as i32,
+                    dt.minute() as i32,
+                    dt.second() as i32,
+                    (dt.nanosecond() / 1000) as i32,
+                    tzinfo,
                     datetime_api.DateTimeType,
                 )
             };
+            if !key.is_empty() {
+                let key_obj = unicode_from_str(key);
+                let zone = pyobject_call_one_arg((*self.state).get_zoneinfo_type().cast(), key_obj);
+                pyo3::ffi::Py_DECREF(key_obj);
+                if !zone.is_null() {
+                    let utc = new_datetime(datetime, zone);
+                    let obj = if utc.is_null() {
+                        utc
+                    } else {
+                        let obj =
+                            pyobject_call_method_one_arg(zone, (*self.state).fromutc_str, utc);
+                        pyo3::ffi::Py_DECREF(utc);
+                        obj
+                    };
+                    pyo3::ffi::Py_DECREF(zone);
+                    if unlikely(obj.is_null()) {
+                        pyo3::ffi::PyErr_Clear();
+                        return Err(Error::InvalidValue);
+                    }
+                    return Ok(NonNull::new_unchecked(obj));
+                }
+                // an unknown zone falls back to its offset at that instant
+                pyo3::ffi::PyErr_Clear();
+            }
+            let local = datetime
+                .checked_add_signed(chrono::TimeDelta::seconds(offset.into()))
+                .ok_or(Error::InvalidValue)?;
+            let tzinfo = if offset == 0 {
+                pyo3::ffi::Py_NewRef(datetime_api.TimeZone_UTC)
+            } else {
+                let delta = (datetime_api.Delta_FromDelta)(0, offset, 0, 1, datetime_api.DeltaType);
+                let tzinfo = (datetime_api.TimeZone_FromTimeZone)(delta, std::ptr::null_mut());
+                pyo3::ffi::Py_DECREF(delta);
+                tzinfo
+            };
+            if unlikely(tzinfo.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
+            let obj = new_datetime(local, tzinfo);
+            pyo3::ffi::Py_DECREF(tzinfo);
+            if unlikely(obj.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
+            Ok(NonNull::new_unchecked(obj))
+        }
+    }
+
+    fn deserialize_timedelta_ext(
+        &mut self,
+        len: u32,
+    ) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let (seconds, nanoseconds) = read_timestamp_value(&mut self.data, len)?;
+        if unlikely(nanoseconds >= 1_000_000_000) {
+            return Err(Error::InvalidValue);
+        }
+        let days = match i32::try_from(seconds.div_euclid(86400)) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::InvalidValue),
+        };
+        unsafe {
+            let datetime_api = *pyo3::ffi::PyDateTimeAPI();
+            let obj = (datetime_api.Delta_FromDelta)(
+                days,
+                seconds.rem_euclid(86400) as i32,
+                (nanoseconds / 1000) as i32,
+                0,
+                datetime_api.DeltaType,
+            );
+            if unlikely(obj.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
+            Ok(NonNull::new_unchecked(obj))
+        }
+    }
+
+    fn deserialize_big_int_ext(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let data = self.data.read_slice(len as usize)?;
+        let ptr = unsafe { pylong_from_be_bytes(data) };
+        if unlikely(ptr.is_null()) {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(Error::Internal);
+        }
+        Ok(unsafe { NonNull::new_unchecked(ptr) })
+    }
+
+    fn deserialize_decimal_ext(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let data = self.data.read_slice(len as usize)?;
+        if unlikely(data.len() < 4) {
+            return Err(Error::InvalidValue);
+        }
+        let scale = i32::from_be_bytes(data[..4].try_into().unwrap());
+        unsafe {
+            let unscaled = pylong_from_be_bytes(&data[4..]);
+            if unlikely(unscaled.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::Internal);
+            }
+            // the string constructor is exact regardless of the context
+            let value =
+                pyo3::ffi::PyUnicode_FromFormat(c"%SE%lld".as_ptr(), unscaled, -(scale as i64));
+            pyo3::ffi::Py_DECREF(unscaled);
+            let ptr = pyobject_call_one_arg((*self.state).decimal_type.cast(), value);
+            pyo3::ffi::Py_DECREF(value);
+            if unlikely(ptr.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::Internal);
+            }
+            Ok(NonNull::new_unchecked(ptr))
+        }
+    }
+
+    fn deserialize_numpy_ext(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let data = self.data.read_slice(len as usize)?;
+        let (descr, shape, items) = parse_numpy_ext(data).ok_or(Error::InvalidValue)?;
+        unsafe {
+            let frombuffer = match (*self.state).get_numpy_types() {
+                Some(numpy_types) => numpy_types.frombuffer,
+                None => return Err(Error::NumpyMissing),
+            };
+            let descr = pyo3::ffi::PyUnicode_FromStringAndSize(
+                descr.as_ptr().cast::<c_char>(),
+                descr.len() as pyo3::ffi::Py_ssize_t,
+            );
+            // a bytearray makes the array writable
+            let buffer = pyo3::ffi::PyByteArray_FromStringAndSize(
+                items.as_ptr().cast::<c_char>(),
+                items.len() as pyo3::ffi::Py_ssize_t,
+            );
+            let array = pyo3::ffi::PyObject_CallFunctionObjArgs(
+                frombuffer,
+                buffer,
+                descr,
+                std::ptr::null_mut::<pyo3::ffi::PyObject>(),
+            );
+            pyo3::ffi::Py_DECREF(buffer);
+            pyo3::ffi::Py_DECREF(descr);
+            if unlikely(array.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
+            let shape_obj = pyo3::ffi::PyTuple_New(shape.len() as pyo3::ffi::Py_ssize_t);
+            for (i, dim) in shape.enumerate() {
+                pytuple_set_item(
+                    shape_obj,
+                    i as isize,
+                    pyo3::ffi::PyLong_FromUnsignedLongLong(dim),
+                );
+            }
+            let obj = pyobject_call_method_one_arg(array, (*self.state).reshape_str, shape_obj);
+            pyo3::ffi::Py_DECREF(shape_obj);
+            pyo3::ffi::Py_DECREF(array);
+            if unlikely(obj.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return Err(Error::InvalidValue);
+            }
             Ok(NonNull::new_unchecked(obj))
         }
     }
 
     fn deserialize_ext(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let tag = self.data.read_i8()?;
+        if tag == -1 && self.opts & TIMESTAMP_EXT_AS_TIMESTAMP != 0 {
+            return self.deserialize_timestamp_object_ext(len);
+        }
         if tag == -1 && self.opts & DATETIME_AS_TIMESTAMP_EXT != 0 {
             return self.deserialize_timestamp_ext(len);
         }
+        if tag == self.ext_tags.big_int && self.opts & BIG_INT_AS_EXT != 0 {
+            return self.deserialize_big_int_ext(len);
+        }
+        if tag == self.ext_tags.datetime && self.opts & DATETIME_AS_TZ_EXT != 0 {
+            return self.deserialize_tz_datetime_ext(len);
+        }
+        if tag == self.ext_tags.decimal && self.opts & DECIMAL_AS_EXT != 0 {
+            return self.deserialize_decimal_ext(len);
+        }
+        if tag == self.ext_tags.timedelta && self.opts & TIMEDELTA_AS_EXT != 0 {
+            return self.deserialize_timedelta_ext(len);
+        }
+        if tag == self.ext_tags.numpy && self.opts & NUMPY_AS_EXT != 0 {
+            return self.deserialize_numpy_ext(len);
+        }
 
         let data = self.data.read_slice(len as usize)?;
 
         match self.ext_hook {
             Some(callable) => unsafe {
+                let data_obj = if self.opts & EXT_AS_MEMORYVIEW != 0 {
+                    self.source.slice(data)?.as_ptr()
+                } else {
+                    let data_ptr = data.as_ptr().cast::<c_char>();
+                    let data_len = data.len() as pyo3::ffi::Py_ssize_t;
+                    pyo3::ffi::PyBytes_FromStringAndSize(data_ptr, data_len)
+                };
                 let tag_obj = pyo3::ffi::PyLong_FromLongLong(tag as i64);
-                let data_ptr = data.as_ptr().cast::<c_char>();
-                let data_len = data.len() as pyo3::ffi::Py_ssize_t;
-                let data_obj = pyo3::ffi::PyBytes_FromStringAndSize(data_ptr, data_len);
                 let obj = pyo3::ffi::PyObject_CallFunctionObjArgs(
                     callable.as_ptr(),
                     tag_obj,
@@ -241,12 +649,16 @@ where
 
     fn deserialize_str(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let data = self.data.read_slice(len as usize)?;
-        let value = from_utf8(data)?;
-        let ptr = unicode_from_str(value);
-        unsafe { Ok(NonNull::new_unchecked(ptr)) }
+        match from_utf8(data) {
+            Ok(value) => {
+                let ptr = unicode_from_str(value);
+                unsafe { Ok(NonNull::new_unchecked(ptr)) }
+            }
+            Err(_) => deserialize_invalid_str(data, self.opts),
+        }
     }
 
-    fn deserialize_bin(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+    fn deserialize_bytes(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let v = self.data.read_slice(len as usize)?;
         let ptr = v.as_ptr().cast::<c_char>();
         let len = v.len() as pyo3::ffi::Py_ssize_t;
@@ -256,6 +668,27 @@ where
         }
     }
 
+    fn deserialize_bytearray(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let v = self.data.read_slice(len as usize)?;
+        let ptr = v.as_ptr().cast::<c_char>();
+        let len = v.len() as pyo3::ffi::Py_ssize_t;
+        unsafe {
+            let ptr = pyo3::ffi::PyByteArray_FromStringAndSize(ptr, len);
+            Ok(NonNull::new_unchecked(ptr))
+        }
+    }
+
+    fn deserialize_bin(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        if self.opts & BIN_AS_MEMORYVIEW != 0 {
+            let v = self.data.read_slice(len as usize)?;
+            self.source.slice(v)
+        } else if self.opts & BIN_AS_BYTEARRAY != 0 {
+            self.deserialize_bytearray(len)
+        } else {
+            self.deserialize_bytes(len)
+        }
+    }
+
     fn deserialize_array(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let ptr = unsafe { pyo3::ffi::PyList_New(len as pyo3::ffi::Py_ssize_t) };
         for i in 0..len {
@@ -448,13 +881,21 @@ where
     }
 
     fn deserialize_map_str_key(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
+        let data = self.data.read_slice(len as usize)?;
         if unlikely(len > 64) {
-            let value = self.deserialize_str(len)?;
-            hash_str(value.as_ptr());
-            Ok(value)
+            match from_utf8(data) {
+                Ok(value) => {
+                    let ptr = unicode_from_str(value);
+                    hash_str(ptr);
+                    unsafe { Ok(NonNull::new_unchecked(ptr)) }
+                }
+                Err(_) => deserialize_invalid_str(data, self.opts),
+            }
         } else {
-            let data = self.data.read_slice(len as usize)?;
-            Ok(unsafe { (*self.state).key_map.get(data)? })
+            match unsafe { (*self.state).key_map.get(data) } {
+                Ok(value) => Ok(value),
+                Err(_) => deserialize_invalid_str(data, self.opts),
+            }
         }
     }
 
@@ -474,8 +915,18 @@ where
 
     fn deserialize_map_ext_key(&mut self, len: u32) -> Result<NonNull<pyo3::ffi::PyObject>, Error> {
         let tag = self.data.read_i8()?;
-        if tag == -1 && self.opts & DATETIME_AS_TIMESTAMP_EXT != 0 {
+        if tag == -1 && self.opts & TIMESTAMP_EXT_AS_TIMESTAMP != 0 {
+            self.deserialize_timestamp_object_ext(len)
+        } else if tag == -1 && self.opts & DATETIME_AS_TIMESTAMP_EXT != 0 {
             self.deserialize_timestamp_ext(len)
+        } else if tag == self.ext_tags.big_int && self.opts & BIG_INT_AS_EXT != 0 {
+            self.deserialize_big_int_ext(len)
+        } else if tag == self.ext_tags.datetime && self.opts & DATETIME_AS_TZ_EXT != 0 {
+            self.deserialize_tz_datetime_ext(len)
+        } else if tag == self.ext_tags.decimal && self.opts & DECIMAL_AS_EXT != 0 {
+            self.deserialize_decimal_ext(len)
+        } else if tag == self.ext_tags.timedelta && self.opts & TIMEDELTA_AS_EXT != 0 {
+            self.deserialize_timedelta_ext(len)
         } else {
             Err(Error::InvalidValue)
         }
@@ -549,15 +1000,15 @@ where
             }
             Marker::Bin8 => {
                 let len = self.data.read_u8()?;
-                self.deserialize_bin(len.into())
+                self.deserialize_bytes(len.into())
             }
             Marker::Bin16 => {
                 let len = self.data.read_u16()?;
-                self.deserialize_bin(len.into())
+                self.deserialize_bytes(len.into())
             }
             Marker::Bin32 => {
                 let len = self.data.read_u32()?;
-                self.deserialize_bin(len)
+                self.deserialize_bytes(len)
             }
             Marker::FixArray(len) => self.deserialize_map_array_key(len.into()),
             Marker::Array16 => {
@@ -568,12 +1019,23 @@ where
                 let len = self.data.read_u32()?;
                 self.deserialize_map_array_key(len)
             }
+            Marker::FixExt1 => self.deserialize_map_ext_key(1),
+            Marker::FixExt2 => self.deserialize_map_ext_key(2),
             Marker::FixExt4 => self.deserialize_map_ext_key(4),
             Marker::FixExt8 => self.deserialize_map_ext_key(8),
+            Marker::FixExt16 => self.deserialize_map_ext_key(16),
             Marker::Ext8 => {
                 let len = self.data.read_u8()?;
                 self.deserialize_map_ext_key(len.into())
             }
+            Marker::Ext16 => {
+                let len = self.data.read_u16()?;
+                self.deserialize_map_ext_key(len.into())
+            }
+            Marker::Ext32 => {
+                let len = self.data.read_u32()?;
+                self.deserialize_map_ext_key(len)
+            }
             marker => Err(Error::InvalidType(marker)),
         };
 
@@ -3,7 +3,9 @@
 mod cache;
 mod deserializer;
 mod error;
+mod target;
 
 pub use cache::KeyMap;
 pub use deserializer::deserialize;
 pub use error::DeserializeError;
+pub use target::construct;
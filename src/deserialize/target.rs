@@ -0,0 +1,468 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::DeserializeError;
+use crate::ffi::*;
+use crate::serialize::{dataclass_fields, is_attrs, is_dataclass, is_pydantic_model};
+use crate::state::State;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// Return the name of the `__init__` parameter of an attrs attribute or a
+/// pydantic field, which is its alias if it has one.
+unsafe fn init_name(
+    name: *mut pyo3::ffi::PyObject,
+    info: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    let alias = pyo3::ffi::PyObject_GetAttr(info, (*state).alias_str);
+    if alias.is_null() {
+        pyo3::ffi::PyErr_Clear();
+    } else if pyo3::ffi::PyUnicode_Check(alias) != 0 {
+        return alias;
+    } else {
+        pyo3::ffi::Py_DECREF(alias);
+    }
+    pyo3::ffi::Py_NewRef(name)
+}
+
+/// Return whether an attrs attribute or a dataclass field is a parameter of
+/// `__init__`.
+unsafe fn is_init(info: *mut pyo3::ffi::PyObject, state: *mut State) -> bool {
+    let init = pyo3::ffi::PyObject_GetAttr(info, (*state).init_str);
+    if init.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return true;
+    }
+    pyo3::ffi::Py_DECREF(init);
+    init != pyo3::ffi::Py_False()
+}
+
+/// Return a new reference to the attribute `name` of `info`, or to `None` if
+/// it is missing.
+unsafe fn annotation(
+    info: *mut pyo3::ffi::PyObject,
+    name: *mut pyo3::ffi::PyObject,
+) -> *mut pyo3::ffi::PyObject {
+    let annotation = pyo3::ffi::PyObject_GetAttr(info, name);
+    if annotation.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None());
+    }
+    annotation
+}
+
+/// Append an `(attr, init_name, annotation)` tuple to `names`, consuming the
+/// references to `init_name` and `annotation`.
+unsafe fn append_name(
+    names: *mut pyo3::ffi::PyObject,
+    attr: *mut pyo3::ffi::PyObject,
+    init_name: *mut pyo3::ffi::PyObject,
+    annotation: *mut pyo3::ffi::PyObject,
+) {
+    let entry = pyo3::ffi::PyTuple_New(3);
+    pytuple_set_item(entry, 0, pyo3::ffi::Py_NewRef(attr));
+    pytuple_set_item(entry, 1, init_name);
+    pytuple_set_item(entry, 2, annotation);
+    pyo3::ffi::PyList_Append(names, entry);
+    pyo3::ffi::Py_DECREF(entry);
+}
+
+unsafe fn dataclass_init_names(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+    names: *mut pyo3::ffi::PyObject,
+) {
+    if let Some(fields) = dataclass_fields(ob_type, state) {
+        let infos = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).dataclass_fields_str);
+        if infos.is_null() {
+            pyo3::ffi::PyErr_Clear();
+        }
+        for i in 0..pyo3::ffi::Py_SIZE(fields.as_ptr()) {
+            let entry = pytuple_get_item(fields.as_ptr(), i);
+            let attr = pytuple_get_item(entry, 0);
+            let init_name = if pytuple_get_item(entry, 3) == pyo3::ffi::Py_True() {
+                attr
+            } else {
+                pyo3::ffi::Py_None()
+            };
+            let info = if !infos.is_null() && pyo3::ffi::PyDict_Check(infos) != 0 {
+                pyo3::ffi::PyDict_GetItem(infos, attr)
+            } else {
+                std::ptr::null_mut()
+            };
+            let annotation = if info.is_null() {
+                pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None())
+            } else {
+                annotation(info, (*state).type_str)
+            };
+            append_name(names, attr, pyo3::ffi::Py_NewRef(init_name), annotation);
+        }
+        pyo3::ffi::Py_XDECREF(infos);
+        pyo3::ffi::Py_DECREF(fields.as_ptr());
+    }
+}
+
+unsafe fn attrs_init_names(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+    names: *mut pyo3::ffi::PyObject,
+) {
+    let attributes = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).attrs_attrs_str);
+    if attributes.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return;
+    }
+    if pyo3::ffi::PyTuple_Check(attributes) != 0 {
+        for i in 0..pyo3::ffi::Py_SIZE(attributes) {
+            let attribute = pytuple_get_item(attributes, i);
+            let name = pyo3::ffi::PyObject_GetAttr(attribute, (*state).name_str);
+            if name.is_null() {
+                pyo3::ffi::PyErr_Clear();
+                continue;
+            }
+            if pyo3::ffi::PyUnicode_Check(name) != 0
+                && !(pyo3::ffi::PyUnicode_GetLength(name) > 0
+                    && pyo3::ffi::PyUnicode_ReadChar(name, 0) == '_' as u32)
+            {
+                let init_name = if is_init(attribute, state) {
+                    init_name(name, attribute, state)
+                } else {
+                    pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None())
+                };
+                append_name(
+                    names,
+                    name,
+                    init_name,
+                    annotation(attribute, (*state).type_str),
+                );
+            }
+            pyo3::ffi::Py_DECREF(name);
+        }
+    }
+    pyo3::ffi::Py_DECREF(attributes);
+}
+
+unsafe fn pydantic_init_names(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+    names: *mut pyo3::ffi::PyObject,
+) {
+    let mut fields = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).model_fields_str);
+    if fields.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        fields = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).fields_str);
+        if fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return;
+        }
+    }
+    if pyo3::ffi::PyDict_Check(fields) != 0 {
+        for (name, info) in PyDictIter::from_pyobject(fields) {
+            let init_name = init_name(name.as_ptr(), info.as_ptr(), state);
+            // FieldInfo.annotation in pydantic v2, ModelField.outer_type_ in v1
+            let mut annotation =
+                pyo3::ffi::PyObject_GetAttrString(info.as_ptr(), c"annotation".as_ptr());
+            if annotation.is_null() {
+                pyo3::ffi::PyErr_Clear();
+                annotation =
+                    pyo3::ffi::PyObject_GetAttrString(info.as_ptr(), c"outer_type_".as_ptr());
+                if annotation.is_null() {
+                    pyo3::ffi::PyErr_Clear();
+                    annotation = pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None());
+                }
+            }
+            append_name(names, name.as_ptr(), init_name, annotation);
+        }
+    }
+    pyo3::ffi::Py_DECREF(fields);
+}
+
+fn is_struct(ob_type: *mut pyo3::ffi::PyTypeObject, state: *mut State) -> bool {
+    is_dataclass(ob_type, state) || is_attrs(ob_type, state) || is_pydantic_model(ob_type, state)
+}
+
+/// Return the `(attr, init_name, annotation)` tuples of the fields of a
+/// dataclass, attrs class or pydantic model, in the order the fields are
+/// serialized by `OPT_STRUCT_AS_ARRAY`, with `None` as `init_name` for the
+/// fields that are not parameters of `__init__`, or null if `ob_type` is not
+/// such a class.
+unsafe fn init_names(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    let names = pyo3::ffi::PyList_New(0);
+    if is_dataclass(ob_type, state) {
+        dataclass_init_names(ob_type, state, names);
+    } else if is_attrs(ob_type, state) {
+        attrs_init_names(ob_type, state, names);
+    } else if is_pydantic_model(ob_type, state) {
+        pydantic_init_names(ob_type, state, names);
+    } else {
+        pyo3::ffi::Py_DECREF(names);
+        return std::ptr::null_mut();
+    }
+    names
+}
+
+/// Return a new reference to a field annotation, evaluating it in the
+/// namespace of the module and of the class if it is a string or a forward
+/// reference, or null if it cannot be evaluated.
+unsafe fn resolve_annotation(
+    annotation: *mut pyo3::ffi::PyObject,
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+) -> *mut pyo3::ffi::PyObject {
+    let source = if pyo3::ffi::PyUnicode_Check(annotation) != 0 {
+        pyo3::ffi::Py_NewRef(annotation)
+    } else {
+        let arg = pyo3::ffi::PyObject_GetAttrString(annotation, c"__forward_arg__".as_ptr());
+        if arg.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return pyo3::ffi::Py_NewRef(annotation);
+        }
+        if pyo3::ffi::PyUnicode_Check(arg) == 0 {
+            pyo3::ffi::Py_DECREF(arg);
+            return pyo3::ffi::Py_NewRef(annotation);
+        }
+        arg
+    };
+    let mut ret = std::ptr::null_mut();
+    let text = pyo3::ffi::PyUnicode_AsUTF8(source);
+    let module_name = pyo3::ffi::PyObject_GetAttrString(ob_type.cast(), c"__module__".as_ptr());
+    let module = if module_name.is_null() {
+        std::ptr::null_mut()
+    } else {
+        pyo3::ffi::PyImport_GetModule(module_name)
+    };
+    let namespace = pyo3::ffi::PyObject_GetAttrString(ob_type.cast(), c"__dict__".as_ptr());
+    if !text.is_null() && !module.is_null() && !namespace.is_null() {
+        let globals = pyo3::ffi::PyModule_GetDict(module);
+        if !globals.is_null() {
+            ret = pyo3::ffi::PyRun_String(text, pyo3::ffi::Py_eval_input, globals, namespace);
+        }
+    }
+    if ret.is_null() {
+        pyo3::ffi::PyErr_Clear();
+    }
+    pyo3::ffi::Py_XDECREF(namespace);
+    pyo3::ffi::Py_XDECREF(module);
+    pyo3::ffi::Py_XDECREF(module_name);
+    pyo3::ffi::Py_DECREF(source);
+    ret
+}
+
+/// Return the class of a field annotated with a dataclass, attrs class or
+/// pydantic model, or with an optional one, as a new reference, or null.
+/// `resolved` is cleared if the annotation cannot be evaluated.
+unsafe fn nested_struct(
+    annotation: *mut pyo3::ffi::PyObject,
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+    resolved: &mut bool,
+) -> *mut pyo3::ffi::PyObject {
+    let hint = resolve_annotation(annotation, ob_type);
+    if hint.is_null() {
+        *resolved = false;
+        return std::ptr::null_mut();
+    }
+    if pyo3::ffi::PyType_Check(hint) != 0 {
+        if is_struct(hint.cast(), state) {
+            return hint;
+        }
+        pyo3::ffi::Py_DECREF(hint);
+        return std::ptr::null_mut();
+    }
+    // Optional[T] and T | None have the __args__ (T, NoneType)
+    let args = pyo3::ffi::PyObject_GetAttrString(hint, c"__args__".as_ptr());
+    pyo3::ffi::Py_DECREF(hint);
+    if args.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return std::ptr::null_mut();
+    }
+    let mut nested = std::ptr::null_mut();
+    if pyo3::ffi::PyTuple_Check(args) != 0 && pyo3::ffi::Py_SIZE(args) == 2 {
+        let none_type = ob_type!(pyo3::ffi::Py_None()).cast::<pyo3::ffi::PyObject>();
+        for (arg, other) in [(0, 1), (1, 0)] {
+            if pytuple_get_item(args, other) != none_type {
+                continue;
+            }
+            let arg = resolve_annotation(pytuple_get_item(args, arg), ob_type);
+            if arg.is_null() {
+                *resolved = false;
+            } else if pyo3::ffi::PyType_Check(arg) != 0 && is_struct(arg.cast(), state) {
+                nested = arg;
+            } else {
+                pyo3::ffi::Py_DECREF(arg);
+            }
+        }
+    }
+    pyo3::ffi::Py_DECREF(args);
+    nested
+}
+
+/// Return the layout of a dataclass, attrs class or pydantic model as a
+/// tuple of `(init_name, nested)` tuples, in the order the fields are
+/// serialized by `OPT_STRUCT_AS_ARRAY`, where `init_name` is the `__init__`
+/// parameter of the field or `None`, and `nested` is the class of the field
+/// if it is annotated with such a class, or `None`. `None` is returned if
+/// `ob_type` is not such a class. The annotations are resolved separately
+/// for each field, and `resolved` is cleared if any of them cannot be
+/// evaluated, such as a forward reference to a class that is not defined
+/// yet.
+fn load_layout(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+    resolved: &mut bool,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let names = init_names(ob_type, state);
+        if names.is_null() {
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        let len = pyo3::ffi::Py_SIZE(names);
+        let layout = pyo3::ffi::PyTuple_New(len);
+        for i in 0..len {
+            let entry = pyo3::ffi::PyList_GET_ITEM(names, i);
+            let mut nested = std::ptr::null_mut();
+            if pytuple_get_item(entry, 1) != pyo3::ffi::Py_None() {
+                nested = nested_struct(pytuple_get_item(entry, 2), ob_type, state, resolved);
+            }
+            if nested.is_null() {
+                nested = pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None());
+            }
+            let item = pyo3::ffi::PyTuple_New(2);
+            pytuple_set_item(item, 0, pyo3::ffi::Py_NewRef(pytuple_get_item(entry, 1)));
+            pytuple_set_item(item, 1, nested);
+            pytuple_set_item(layout, i, item);
+        }
+        pyo3::ffi::Py_DECREF(names);
+        NonNull::new(layout)
+    }
+}
+
+/// Return a new reference to the layout of `target`, cached per class unless
+/// some of its annotations could not be resolved, or null if it is not a
+/// dataclass, attrs class or pydantic model.
+unsafe fn struct_layout(
+    target: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    if pyo3::ffi::PyType_Check(target) == 0 {
+        return std::ptr::null_mut();
+    }
+    let ob_type = target.cast::<pyo3::ffi::PyTypeObject>();
+    let mut uncached = None;
+    let layout = (*state).struct_layouts.get(ob_type, || {
+        let mut resolved = true;
+        let layout = load_layout(ob_type, state, &mut resolved);
+        if resolved {
+            return layout;
+        }
+        uncached = layout;
+        None
+    });
+    match layout.or(uncached) {
+        Some(layout) if layout.as_ptr() != pyo3::ffi::Py_None() => layout.as_ptr(),
+        Some(layout) => {
+            pyo3::ffi::Py_DECREF(layout.as_ptr());
+            std::ptr::null_mut()
+        }
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Call `target` with the elements of an array as keyword arguments named
+/// by `layout`. The elements that are arrays are constructed as instances
+/// of the nested class of their field, if any.
+unsafe fn call_with_layout(
+    target: *mut pyo3::ffi::PyObject,
+    obj: *mut pyo3::ffi::PyObject,
+    layout: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> Result<*mut pyo3::ffi::PyObject, DeserializeError<'static>> {
+    let len = pyo3::ffi::Py_SIZE(layout);
+    if pyo3::ffi::Py_SIZE(obj) != len {
+        return Err(DeserializeError::new(Cow::Borrowed(
+            "The array does not match the fields of the type argument",
+        )));
+    }
+    let kwargs = pyo3::ffi::PyDict_New();
+    for i in 0..len {
+        let entry = pytuple_get_item(layout, i);
+        let name = pytuple_get_item(entry, 0);
+        if name == pyo3::ffi::Py_None() {
+            continue;
+        }
+        let value = pyo3::ffi::PyList_GET_ITEM(obj, i);
+        let nested = pytuple_get_item(entry, 1);
+        if nested != pyo3::ffi::Py_None() && pyo3::ffi::PyList_CheckExact(value) != 0 {
+            let nested = NonNull::new_unchecked(nested);
+            match construct(
+                nested,
+                NonNull::new_unchecked(pyo3::ffi::Py_NewRef(value)),
+                state,
+            ) {
+                Ok(value) => {
+                    pyo3::ffi::PyDict_SetItem(kwargs, name, value.as_ptr());
+                    pyo3::ffi::Py_DECREF(value.as_ptr());
+                }
+                Err(err) => {
+                    pyo3::ffi::Py_DECREF(kwargs);
+                    return Err(err);
+                }
+            }
+        } else {
+            pyo3::ffi::PyDict_SetItem(kwargs, name, value);
+        }
+    }
+    let args = pyo3::ffi::PyTuple_New(0);
+    let ret = pyo3::ffi::PyObject_Call(target, args, kwargs);
+    pyo3::ffi::Py_DECREF(args);
+    pyo3::ffi::Py_DECREF(kwargs);
+    Ok(ret)
+}
+
+/// Construct an instance of `target` from a deserialized object, by calling
+/// it with the items of a map as keyword arguments or with the elements of
+/// an array as positional arguments. The elements of an array are passed as
+/// keyword arguments to dataclasses, attrs classes and pydantic models, in
+/// the order their fields are serialized, and the nested arrays of fields
+/// annotated with such classes are constructed recursively. The reference
+/// to `obj` is consumed.
+pub fn construct(
+    target: NonNull<pyo3::ffi::PyObject>,
+    obj: NonNull<pyo3::ffi::PyObject>,
+    state: *mut State,
+) -> Result<NonNull<pyo3::ffi::PyObject>, DeserializeError<'static>> {
+    let obj = obj.as_ptr();
+    let ret = unsafe {
+        if pyo3::ffi::PyDict_CheckExact(obj) != 0 {
+            let args = pyo3::ffi::PyTuple_New(0);
+            let ret = pyo3::ffi::PyObject_Call(target.as_ptr(), args, obj);
+            pyo3::ffi::Py_DECREF(args);
+            Ok(ret)
+        } else if pyo3::ffi::PyList_CheckExact(obj) != 0 {
+            let layout = struct_layout(target.as_ptr(), state);
+            if layout.is_null() {
+                let args = pyo3::ffi::PyList_AsTuple(obj);
+                let ret = pyo3::ffi::PyObject_Call(target.as_ptr(), args, std::ptr::null_mut());
+                pyo3::ffi::Py_DECREF(args);
+                Ok(ret)
+            } else {
+                let ret = call_with_layout(target.as_ptr(), obj, layout, state);
+                pyo3::ffi::Py_DECREF(layout);
+                ret
+            }
+        } else {
+            Err(DeserializeError::new(Cow::Borrowed(
+                "The type argument requires a map or an array",
+            )))
+        }
+    };
+    unsafe { pyo3::ffi::Py_DECREF(obj) };
+    match NonNull::new(ret?) {
+        Some(ret) => Ok(ret),
+        None => Err(DeserializeError::new(Cow::Borrowed(
+            "The type argument could not be called with the deserialized object",
+        ))),
+    }
+}
@@ -41,6 +41,50 @@ pub fn pylong_to_u64(op: *mut PyObject) -> Option<u64> {
     }
 }
 
+/// Write `op` to `buffer` in two's complement big-endian representation.
+pub unsafe fn pylong_to_be_bytes(op: *mut PyObject, buffer: &mut [u8]) {
+    #[cfg(Py_3_13)]
+    {
+        PyLong_AsNativeBytes(
+            op,
+            buffer.as_mut_ptr().cast(),
+            buffer.len() as Py_ssize_t,
+            Py_ASNATIVEBYTES_BIG_ENDIAN,
+        );
+    }
+    #[cfg(not(Py_3_13))]
+    {
+        _PyLong_AsByteArray(
+            op.cast::<PyLongObject>(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            0, // little_endian
+            1, // is_signed
+        );
+    }
+}
+
+/// Create an int from its two's complement big-endian representation.
+pub unsafe fn pylong_from_be_bytes(data: &[u8]) -> *mut PyObject {
+    #[cfg(Py_3_13)]
+    {
+        PyLong_FromNativeBytes(
+            data.as_ptr().cast(),
+            data.len(),
+            Py_ASNATIVEBYTES_BIG_ENDIAN,
+        )
+    }
+    #[cfg(not(Py_3_13))]
+    {
+        _PyLong_FromByteArray(
+            data.as_ptr(),
+            data.len(),
+            0, // little_endian
+            1, // is_signed
+        )
+    }
+}
+
 // https://tools.ietf.org/html/rfc7159#section-6
 // "[-(2**53)+1, (2**53)-1]"
 
@@ -63,6 +63,48 @@ impl Iterator for PyDictIter {
     }
 }
 
+pub struct PyIter {
+    iter: *mut PyObject,
+    item: *mut PyObject,
+}
+
+impl PyIter {
+    #[inline]
+    pub unsafe fn from_pyobject(op: *mut PyObject) -> Option<Self> {
+        let iter = PyObject_GetIter(op);
+        if iter.is_null() {
+            None
+        } else {
+            Some(PyIter {
+                iter: iter,
+                item: std::ptr::null_mut(),
+            })
+        }
+    }
+}
+
+impl Iterator for PyIter {
+    type Item = NonNull<PyObject>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            Py_XDECREF(self.item);
+            self.item = PyIter_Next(self.iter);
+            NonNull::new(self.item)
+        }
+    }
+}
+
+impl Drop for PyIter {
+    fn drop(&mut self) {
+        unsafe {
+            Py_XDECREF(self.item);
+            Py_DECREF(self.iter);
+        }
+    }
+}
+
 pub struct Buffer {
     view: Py_buffer,
 }
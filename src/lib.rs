@@ -22,13 +22,13 @@ mod opt;
 mod serialize;
 mod state;
 mod str;
+mod timestamp;
 
 use crate::ffi::*;
 use pyo3::ffi::*;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::os::raw::c_int;
-use std::os::raw::c_long;
 use std::os::raw::c_void;
 use std::ptr::NonNull;
 
@@ -45,7 +45,7 @@ macro_rules! module_add_object {
 
 macro_rules! module_add_int {
     ($mptr:expr, $name:expr, $int:expr) => {
-        PyModule_AddIntConstant($mptr, $name.as_ptr(), $int as c_long);
+        PyModule_AddObject($mptr, $name.as_ptr(), PyLong_FromUnsignedLongLong($int));
     };
 }
 
@@ -129,15 +129,44 @@ pub unsafe extern "C" fn ormsgpack_exec(mptr: *mut PyObject) -> c_int {
     module_add_object!(mptr, c"Fragment", (*state).fragment_type.cast::<PyObject>());
     module_add_object!(mptr, c"MsgpackDecodeError", (*state).MsgpackDecodeError);
     module_add_object!(mptr, c"MsgpackEncodeError", (*state).MsgpackEncodeError);
+    module_add_object!(
+        mptr,
+        c"Timestamp",
+        (*state).timestamp_type.cast::<PyObject>()
+    );
 
+    module_add_int!(mptr, c"OPT_BIG_INT_AS_EXT", opt::BIG_INT_AS_EXT);
+    module_add_int!(mptr, c"OPT_BIN_AS_BYTEARRAY", opt::BIN_AS_BYTEARRAY);
+    module_add_int!(mptr, c"OPT_BIN_AS_MEMORYVIEW", opt::BIN_AS_MEMORYVIEW);
     module_add_int!(
         mptr,
         c"OPT_DATETIME_AS_TIMESTAMP_EXT",
         opt::DATETIME_AS_TIMESTAMP_EXT
     );
+    module_add_int!(mptr, c"OPT_DATETIME_AS_TZ_EXT", opt::DATETIME_AS_TZ_EXT);
+    module_add_int!(mptr, c"OPT_DECIMAL_AS_EXT", opt::DECIMAL_AS_EXT);
+    module_add_int!(mptr, c"OPT_ENUM_BY_NAME", opt::ENUM_BY_NAME);
+    module_add_int!(
+        mptr,
+        c"OPT_ENUM_BY_QUALIFIED_NAME",
+        opt::ENUM_BY_QUALIFIED_NAME
+    );
+    module_add_int!(mptr, c"OPT_EXT_AS_MEMORYVIEW", opt::EXT_AS_MEMORYVIEW);
+    module_add_int!(mptr, c"OPT_INVALID_STR_AS_BYTES", opt::INVALID_STR_AS_BYTES);
+    module_add_int!(mptr, c"OPT_INVALID_STR_REPLACE", opt::INVALID_STR_REPLACE);
+    module_add_int!(
+        mptr,
+        c"OPT_INVALID_STR_SURROGATEESCAPE",
+        opt::INVALID_STR_SURROGATEESCAPE
+    );
     module_add_int!(mptr, c"OPT_NAIVE_UTC", opt::NAIVE_UTC);
+    module_add_int!(mptr, c"OPT_NAMEDTUPLE_AS_MAP", opt::NAMEDTUPLE_AS_MAP);
     module_add_int!(mptr, c"OPT_NON_STR_KEYS", opt::NON_STR_KEYS);
+    module_add_int!(mptr, c"OPT_NUMPY_AS_EXT", opt::NUMPY_AS_EXT);
+    module_add_int!(mptr, c"OPT_OMIT_DEFAULTS", opt::OMIT_DEFAULTS);
     module_add_int!(mptr, c"OPT_OMIT_MICROSECONDS", opt::OMIT_MICROSECONDS);
+    module_add_int!(mptr, c"OPT_OMIT_NONE", opt::OMIT_NONE);
+    module_add_int!(mptr, c"OPT_PASSTHROUGH_ATTRS", opt::PASSTHROUGH_ATTRS);
     module_add_int!(mptr, c"OPT_PASSTHROUGH_BIG_INT", opt::PASSTHROUGH_BIG_INT);
     module_add_int!(
         mptr,
@@ -149,10 +178,40 @@ pub unsafe extern "C" fn ormsgpack_exec(mptr: *mut PyObject) -> c_int {
     module_add_int!(mptr, c"OPT_PASSTHROUGH_SUBCLASS", opt::PASSTHROUGH_SUBCLASS);
     module_add_int!(mptr, c"OPT_PASSTHROUGH_TUPLE", opt::PASSTHROUGH_TUPLE);
     module_add_int!(mptr, c"OPT_PASSTHROUGH_UUID", opt::PASSTHROUGH_UUID);
+    module_add_int!(mptr, c"OPT_PYDANTIC_BY_ALIAS", opt::PYDANTIC_BY_ALIAS);
+    module_add_int!(
+        mptr,
+        c"OPT_PYDANTIC_COMPUTED_FIELDS",
+        opt::PYDANTIC_COMPUTED_FIELDS
+    );
+    module_add_int!(mptr, c"OPT_PYDANTIC_EXCLUDE", opt::PYDANTIC_EXCLUDE);
     module_add_int!(mptr, c"OPT_REPLACE_SURROGATES", opt::REPLACE_SURROGATES);
+    module_add_int!(
+        mptr,
+        c"OPT_SERIALIZE_COLLECTIONS",
+        opt::SERIALIZE_COLLECTIONS
+    );
+    module_add_int!(mptr, c"OPT_SERIALIZE_DECIMAL", opt::SERIALIZE_DECIMAL);
+    module_add_int!(mptr, c"OPT_SERIALIZE_ITERABLES", opt::SERIALIZE_ITERABLES);
     module_add_int!(mptr, c"OPT_SERIALIZE_NUMPY", opt::SERIALIZE_NUMPY);
     module_add_int!(mptr, c"OPT_SERIALIZE_PYDANTIC", opt::SERIALIZE_PYDANTIC);
+    module_add_int!(mptr, c"OPT_SERIALIZE_SETS", opt::SERIALIZE_SETS);
+    module_add_int!(mptr, c"OPT_SERIALIZE_TIMEDELTA", opt::SERIALIZE_TIMEDELTA);
     module_add_int!(mptr, c"OPT_SORT_KEYS", opt::SORT_KEYS);
+    module_add_int!(mptr, c"OPT_SORT_SETS", opt::SORT_SETS);
+    module_add_int!(mptr, c"OPT_STRUCT_AS_ARRAY", opt::STRUCT_AS_ARRAY);
+    module_add_int!(mptr, c"OPT_TIMEDELTA_AS_EXT", opt::TIMEDELTA_AS_EXT);
+    module_add_int!(
+        mptr,
+        c"OPT_TIMEDELTA_AS_MICROSECONDS",
+        opt::TIMEDELTA_AS_MICROSECONDS
+    );
+    module_add_int!(mptr, c"OPT_TIMEDELTA_AS_SECONDS", opt::TIMEDELTA_AS_SECONDS);
+    module_add_int!(
+        mptr,
+        c"OPT_TIMESTAMP_EXT_AS_TIMESTAMP",
+        opt::TIMESTAMP_EXT_AS_TIMESTAMP
+    );
     module_add_int!(mptr, c"OPT_UTC_Z", opt::UTC_Z);
 
     0
@@ -184,9 +243,9 @@ fn raise_packb_exception(state: *mut state::State, msg: &str) -> *mut PyObject {
     std::ptr::null_mut()
 }
 
-unsafe fn parse_option_arg(opts: *mut PyObject, mask: i32) -> Result<i32, ()> {
+unsafe fn parse_option_arg(opts: *mut PyObject, mask: i64) -> Result<i64, ()> {
     if Py_TYPE(opts) == &raw mut PyLong_Type {
-        let val = PyLong_AsLong(opts) as i32;
+        let val = PyLong_AsLongLong(opts);
         if val & !mask == 0 {
             Ok(val)
         } else {
@@ -199,6 +258,37 @@ unsafe fn parse_option_arg(opts: *mut PyObject, mask: i32) -> Result<i32, ()> {
     }
 }
 
+/// Parse `value` into the field of `tags` named by the keyword argument `arg`,
+/// returning `Ok(false)` if `arg` does not name an extension type.
+unsafe fn parse_ext_tag_kwarg(
+    state: *mut state::State,
+    tags: &mut opt::ExtTags,
+    arg: *mut PyObject,
+    value: *mut PyObject,
+) -> Result<bool, &'static str> {
+    let (tag, msg) = if PyUnicode_Compare(arg, (*state).big_int_tag_str) == 0 {
+        (&mut tags.big_int, "Invalid big_int_tag")
+    } else if PyUnicode_Compare(arg, (*state).datetime_tag_str) == 0 {
+        (&mut tags.datetime, "Invalid datetime_tag")
+    } else if PyUnicode_Compare(arg, (*state).decimal_tag_str) == 0 {
+        (&mut tags.decimal, "Invalid decimal_tag")
+    } else if PyUnicode_Compare(arg, (*state).numpy_tag_str) == 0 {
+        (&mut tags.numpy, "Invalid numpy_tag")
+    } else if PyUnicode_Compare(arg, (*state).timedelta_tag_str) == 0 {
+        (&mut tags.timedelta, "Invalid timedelta_tag")
+    } else {
+        return Ok(false);
+    };
+    if Py_TYPE(value) == &raw mut PyLong_Type {
+        let val = PyLong_AsLong(value);
+        if (0..=127).contains(&val) {
+            *tag = val as i8;
+            return Ok(true);
+        }
+    }
+    Err(msg)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn unpackb(
     module: *mut PyObject,
@@ -208,7 +298,10 @@ pub unsafe extern "C" fn unpackb(
 ) -> *mut PyObject {
     let state: *mut state::State = PyModule_GetState(module).cast();
     let mut ext_hook: Option<NonNull<PyObject>> = None;
+    let mut tzinfo: Option<NonNull<PyObject>> = None;
+    let mut target: Option<NonNull<PyObject>> = None;
     let mut optsptr: Option<NonNull<PyObject>> = None;
+    let mut ext_tags = opt::ExtTags::default();
 
     let num_args = PyVectorcall_NARGS(nargs as usize);
     if num_args != 1 {
@@ -227,16 +320,39 @@ pub unsafe extern "C" fn unpackb(
                 ext_hook = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
             } else if PyUnicode_Compare(arg, (*state).option_str) == 0 {
                 optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+            } else if PyUnicode_Compare(arg, (*state).tzinfo_str) == 0 {
+                let value = *args.offset(num_args + i);
+                if value != Py_None() {
+                    if PyTZInfo_Check(value) == 0 {
+                        return raise_unpackb_exception(state, "Invalid tzinfo");
+                    }
+                    tzinfo = Some(NonNull::new_unchecked(value));
+                }
+            } else if PyUnicode_Compare(arg, (*state).type_str) == 0 {
+                let value = *args.offset(num_args + i);
+                if value != Py_None() {
+                    if PyType_Check(value) == 0 {
+                        return raise_unpackb_exception(state, "Invalid type");
+                    }
+                    target = Some(NonNull::new_unchecked(value));
+                }
             } else {
-                return raise_unpackb_exception(
-                    state,
-                    "unpackb() got an unexpected keyword argument",
-                );
+                let value = *args.offset(num_args + i);
+                match parse_ext_tag_kwarg(state, &mut ext_tags, arg, value) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return raise_unpackb_exception(
+                            state,
+                            "unpackb() got an unexpected keyword argument",
+                        );
+                    }
+                    Err(msg) => return raise_unpackb_exception(state, msg),
+                }
             }
         }
     }
 
-    let mut optsbits: i32 = 0;
+    let mut optsbits: i64 = 0;
     if let Some(opts) = optsptr {
         match parse_option_arg(opts.as_ptr(), opt::UNPACKB_OPT_MASK) {
             Ok(val) => optsbits = val,
@@ -244,7 +360,18 @@ pub unsafe extern "C" fn unpackb(
         }
     }
 
-    match crate::deserialize::deserialize(*args, state, ext_hook, optsbits as opt::Opt) {
+    match crate::deserialize::deserialize(
+        *args,
+        state,
+        ext_hook,
+        tzinfo,
+        optsbits as opt::Opt,
+        ext_tags,
+    )
+    .and_then(|val| match target {
+        Some(target) => crate::deserialize::construct(target, val, state),
+        None => Ok(val),
+    }) {
         Ok(val) => val.as_ptr(),
         Err(err) => raise_unpackb_exception(state, &err.message),
     }
@@ -259,7 +386,9 @@ pub unsafe extern "C" fn packb(
 ) -> *mut PyObject {
     let state: *mut state::State = PyModule_GetState(module).cast();
     let mut default: Option<NonNull<PyObject>> = None;
+    let mut ext_types: Option<NonNull<PyObject>> = None;
     let mut optsptr: Option<NonNull<PyObject>> = None;
+    let mut ext_tags = opt::ExtTags::default();
 
     let num_args = PyVectorcall_NARGS(nargs as usize);
     if num_args == 0 {
@@ -286,6 +415,14 @@ pub unsafe extern "C" fn packb(
                     );
                 }
                 default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
+            } else if PyUnicode_Compare(arg, (*state).ext_types_str) == 0 {
+                let value = *args.offset(num_args + i);
+                if value != Py_None() {
+                    if let Err(msg) = serialize::validate_ext_types(value) {
+                        return raise_packb_exception(state, msg);
+                    }
+                    ext_types = Some(NonNull::new_unchecked(value));
+                }
             } else if PyUnicode_Compare(arg, (*state).option_str) == 0 {
                 if optsptr.is_some() {
                     return raise_packb_exception(
@@ -295,12 +432,22 @@ pub unsafe extern "C" fn packb(
                 }
                 optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
             } else {
-                return raise_packb_exception(state, "packb() got an unexpected keyword argument");
+                let value = *args.offset(num_args + i);
+                match parse_ext_tag_kwarg(state, &mut ext_tags, arg, value) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return raise_packb_exception(
+                            state,
+                            "packb() got an unexpected keyword argument",
+                        );
+                    }
+                    Err(msg) => return raise_packb_exception(state, msg),
+                }
             }
         }
     }
 
-    let mut optsbits: i32 = 0;
+    let mut optsbits: i64 = 0;
     if let Some(opts) = optsptr {
         match parse_option_arg(opts.as_ptr(), opt::PACKB_OPT_MASK) {
             Ok(val) => optsbits = val,
@@ -308,7 +455,14 @@ pub unsafe extern "C" fn packb(
         }
     }
 
-    match crate::serialize::serialize(*args, state, default, optsbits as opt::Opt) {
+    match crate::serialize::serialize(
+        *args,
+        state,
+        default,
+        ext_types,
+        optsbits as opt::Opt,
+        ext_tags,
+    ) {
         Ok(val) => val.as_ptr(),
         Err(err) => raise_packb_exception(state, &err),
     }
@@ -3,6 +3,12 @@
 use crate::io::{Read, WriteSlices};
 use crate::msgpack::marker::Marker;
 
+/// Return the variant index that encodes an extension type when passed to
+/// `serialize_newtype_variant`.
+pub const fn ext_variant_index(tag: i8) -> u32 {
+    tag as u8 as u32
+}
+
 pub fn write_ext<W>(writer: &mut W, value: &[u8], tag: i8) -> Result<(), std::io::Error>
 where
     W: WriteSlices,
@@ -39,10 +45,7 @@ where
     }
 }
 
-pub fn read_timestamp<R>(
-    reader: &mut R,
-    len: u32,
-) -> Result<chrono::DateTime<chrono::Utc>, std::io::Error>
+pub fn read_timestamp_value<R>(reader: &mut R, len: u32) -> Result<(i64, u32), std::io::Error>
 where
     R: Read,
 {
@@ -62,27 +65,37 @@ where
         }
         _ => return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput)),
     };
+    Ok((seconds, nanoseconds))
+}
+
+pub fn read_timestamp<R>(
+    reader: &mut R,
+    len: u32,
+) -> Result<chrono::DateTime<chrono::Utc>, std::io::Error>
+where
+    R: Read,
+{
+    let (seconds, nanoseconds) = read_timestamp_value(reader, len)?;
     match chrono::DateTime::<chrono::Utc>::from_timestamp(seconds, nanoseconds) {
         Some(value) => Ok(value),
         None => Err(std::io::Error::from(std::io::ErrorKind::InvalidInput)),
     }
 }
 
-pub fn write_timestamp<W>(
+pub fn write_timestamp_value<W>(
     writer: &mut W,
-    datetime: chrono::DateTime<chrono::Utc>,
+    seconds: i64,
+    nanoseconds: u32,
 ) -> Result<(), std::io::Error>
 where
     W: std::io::Write,
 {
-    let seconds = datetime.timestamp();
-    let nanoseconds = datetime.timestamp_subsec_nanos();
     if seconds >> 34 == 0 {
-        let value = (i64::from(nanoseconds) << 34) | seconds;
+        let value = (u64::from(nanoseconds) << 34) | seconds as u64;
         if value <= 4294967295 {
             writer.write_all(&(value as u32).to_be_bytes())?;
         } else {
-            writer.write_all(&(value as u64).to_be_bytes())?;
+            writer.write_all(&value.to_be_bytes())?;
         }
     } else {
         writer.write_all(&nanoseconds.to_be_bytes())?;
@@ -90,3 +103,17 @@ where
     }
     Ok(())
 }
+
+pub fn write_timestamp<W>(
+    writer: &mut W,
+    datetime: chrono::DateTime<chrono::Utc>,
+) -> Result<(), std::io::Error>
+where
+    W: std::io::Write,
+{
+    write_timestamp_value(
+        writer,
+        datetime.timestamp(),
+        datetime.timestamp_subsec_nanos(),
+    )
+}
@@ -621,10 +621,7 @@ where
     where
         T: ?Sized + ser::Serialize,
     {
-        let tag: i8 = match variant_index {
-            128 => -1,
-            _ => variant_index.try_into().unwrap_or_else(|_| unreachable!()),
-        };
+        let tag = variant_index as u8 as i8;
         let mut ext_se = ExtSerializer::new(tag, &mut self.writer);
         value.serialize(&mut ext_se)
     }
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-pub type Opt = u16;
+pub type Opt = u64;
 
 pub const NAIVE_UTC: Opt = 1;
 pub const NON_STR_KEYS: Opt = 1 << 1;
@@ -18,11 +18,51 @@ pub const PASSTHROUGH_UUID: Opt = 1 << 12;
 pub const PASSTHROUGH_ENUM: Opt = 1 << 13;
 pub const DATETIME_AS_TIMESTAMP_EXT: Opt = 1 << 14;
 pub const REPLACE_SURROGATES: Opt = 1 << 15;
+pub const BIN_AS_MEMORYVIEW: Opt = 1 << 16;
+pub const EXT_AS_MEMORYVIEW: Opt = 1 << 17;
+pub const BIN_AS_BYTEARRAY: Opt = 1 << 18;
+pub const INVALID_STR_AS_BYTES: Opt = 1 << 19;
+pub const INVALID_STR_REPLACE: Opt = 1 << 20;
+pub const INVALID_STR_SURROGATEESCAPE: Opt = 1 << 21;
+pub const BIG_INT_AS_EXT: Opt = 1 << 22;
+pub const SERIALIZE_DECIMAL: Opt = 1 << 23;
+pub const DECIMAL_AS_EXT: Opt = 1 << 24;
+pub const SERIALIZE_TIMEDELTA: Opt = 1 << 25;
+pub const TIMEDELTA_AS_EXT: Opt = 1 << 26;
+pub const TIMEDELTA_AS_MICROSECONDS: Opt = 1 << 27;
+pub const TIMEDELTA_AS_SECONDS: Opt = 1 << 28;
+pub const DATETIME_AS_TZ_EXT: Opt = 1 << 29;
+pub const TIMESTAMP_EXT_AS_TIMESTAMP: Opt = 1 << 30;
+pub const SERIALIZE_SETS: Opt = 1 << 31;
+pub const SORT_SETS: Opt = 1 << 32;
+pub const SERIALIZE_ITERABLES: Opt = 1 << 33;
+pub const SERIALIZE_COLLECTIONS: Opt = 1 << 34;
+pub const NAMEDTUPLE_AS_MAP: Opt = 1 << 35;
+pub const PASSTHROUGH_ATTRS: Opt = 1 << 36;
+pub const PYDANTIC_BY_ALIAS: Opt = 1 << 37;
+pub const PYDANTIC_EXCLUDE: Opt = 1 << 38;
+pub const PYDANTIC_COMPUTED_FIELDS: Opt = 1 << 39;
+pub const OMIT_NONE: Opt = 1 << 40;
+pub const OMIT_DEFAULTS: Opt = 1 << 41;
+pub const STRUCT_AS_ARRAY: Opt = 1 << 42;
+pub const ENUM_BY_NAME: Opt = 1 << 43;
+pub const ENUM_BY_QUALIFIED_NAME: Opt = 1 << 44;
+pub const NUMPY_AS_EXT: Opt = 1 << 45;
 
-pub const PACKB_OPT_MASK: i32 = (DATETIME_AS_TIMESTAMP_EXT
+pub const PACKB_OPT_MASK: i64 = (BIG_INT_AS_EXT
+    | DATETIME_AS_TIMESTAMP_EXT
+    | DATETIME_AS_TZ_EXT
+    | DECIMAL_AS_EXT
+    | ENUM_BY_NAME
+    | ENUM_BY_QUALIFIED_NAME
     | NAIVE_UTC
+    | NAMEDTUPLE_AS_MAP
     | NON_STR_KEYS
+    | NUMPY_AS_EXT
+    | OMIT_DEFAULTS
     | OMIT_MICROSECONDS
+    | OMIT_NONE
+    | PASSTHROUGH_ATTRS
     | PASSTHROUGH_BIG_INT
     | PASSTHROUGH_DATACLASS
     | PASSTHROUGH_DATETIME
@@ -30,10 +70,60 @@ pub const PACKB_OPT_MASK: i32 = (DATETIME_AS_TIMESTAMP_EXT
     | PASSTHROUGH_SUBCLASS
     | PASSTHROUGH_TUPLE
     | PASSTHROUGH_UUID
+    | PYDANTIC_BY_ALIAS
+    | PYDANTIC_COMPUTED_FIELDS
+    | PYDANTIC_EXCLUDE
     | REPLACE_SURROGATES
+    | SERIALIZE_COLLECTIONS
+    | SERIALIZE_DECIMAL
+    | SERIALIZE_ITERABLES
     | SERIALIZE_NUMPY
     | SERIALIZE_PYDANTIC
+    | SERIALIZE_SETS
+    | SERIALIZE_TIMEDELTA
     | SORT_KEYS
-    | UTC_Z) as i32;
+    | SORT_SETS
+    | STRUCT_AS_ARRAY
+    | TIMEDELTA_AS_EXT
+    | TIMEDELTA_AS_MICROSECONDS
+    | TIMEDELTA_AS_SECONDS
+    | UTC_Z) as i64;
 
-pub const UNPACKB_OPT_MASK: i32 = (DATETIME_AS_TIMESTAMP_EXT | NON_STR_KEYS) as i32;
+pub const UNPACKB_OPT_MASK: i64 = (BIG_INT_AS_EXT
+    | BIN_AS_BYTEARRAY
+    | BIN_AS_MEMORYVIEW
+    | DATETIME_AS_TIMESTAMP_EXT
+    | DATETIME_AS_TZ_EXT
+    | DECIMAL_AS_EXT
+    | EXT_AS_MEMORYVIEW
+    | INVALID_STR_AS_BYTES
+    | INVALID_STR_REPLACE
+    | INVALID_STR_SURROGATEESCAPE
+    | NAIVE_UTC
+    | NON_STR_KEYS
+    | NUMPY_AS_EXT
+    | TIMEDELTA_AS_EXT
+    | TIMESTAMP_EXT_AS_TIMESTAMP) as i64;
+
+/// The extension types of the objects serialized as extension types by the
+/// `*_AS_EXT` options.
+#[derive(Clone, Copy)]
+pub struct ExtTags {
+    pub big_int: i8,
+    pub datetime: i8,
+    pub decimal: i8,
+    pub numpy: i8,
+    pub timedelta: i8,
+}
+
+impl Default for ExtTags {
+    fn default() -> Self {
+        ExtTags {
+            big_int: 0,
+            datetime: 3,
+            decimal: 1,
+            numpy: 4,
+            timedelta: 2,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::*;
+use crate::opt::*;
+use crate::serialize::default::DefaultHook;
+use crate::serialize::serializer::*;
+use crate::state::State;
+use crate::util::unlikely;
+
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use smallvec::SmallVec;
+
+#[inline]
+pub fn is_attrs(ob_type: *mut pyo3::ffi::PyTypeObject, state: *mut State) -> bool {
+    unsafe {
+        let tp_dict = (*ob_type).tp_dict;
+        !tp_dict.is_null() && pyo3::ffi::PyDict_Contains(tp_dict, (*state).attrs_attrs_str) == 1
+    }
+}
+
+pub struct Attrs<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> Attrs<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        Attrs {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Serialize for Attrs<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields =
+            unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).attrs_attrs_str) };
+        unsafe { pyo3::ffi::Py_DECREF(fields) };
+        if unlikely(unsafe { pyo3::ffi::PyTuple_Check(fields) } == 0) {
+            return Err(serde::ser::Error::custom("__attrs_attrs__ must be a tuple"));
+        }
+        let len = unsafe { pyo3::ffi::Py_SIZE(fields) } as usize;
+        if unlikely(len == 0) {
+            if self.opts & STRUCT_AS_ARRAY != 0 {
+                return serializer.serialize_seq(Some(0))?.end();
+            }
+            return serializer.serialize_map(Some(0))?.end();
+        }
+
+        let dict = unsafe { pyo3::ffi::PyObject_GenericGetDict(self.ptr, std::ptr::null_mut()) };
+        if dict.is_null() {
+            // Slotted classes have no __dict__.
+            unsafe { pyo3::ffi::PyErr_Clear() };
+        } else {
+            unsafe { pyo3::ffi::Py_DECREF(dict) };
+        }
+
+        let mut items: SmallVec<[(&str, *mut pyo3::ffi::PyObject); 8]> =
+            SmallVec::with_capacity(len);
+        for i in 0..len {
+            let field = unsafe { pytuple_get_item(fields, i as isize) };
+            let attr = unsafe { pyo3::ffi::PyObject_GetAttr(field, (*self.state).name_str) };
+            if unlikely(attr.is_null()) {
+                return Err(serde::ser::Error::custom("attrs attribute has no name"));
+            }
+            unsafe { pyo3::ffi::Py_DECREF(attr) };
+            if unlikely(unsafe { pyo3::ffi::PyUnicode_Check(attr) } == 0) {
+                return Err(serde::ser::Error::custom(
+                    "attrs attribute name must be str",
+                ));
+            }
+            let key_as_str = unicode_to_str(attr).map_err(serde::ser::Error::custom)?;
+            if key_as_str.as_bytes().first() == Some(&b'_') {
+                continue;
+            }
+
+            let mut value = if dict.is_null() {
+                std::ptr::null_mut()
+            } else {
+                unsafe { pyo3::ffi::PyDict_GetItem(dict, attr) }
+            };
+            if value.is_null() {
+                value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, attr) };
+                if unlikely(value.is_null()) {
+                    return Err(serde::ser::Error::custom(format!(
+                        "attrs attribute is not set: {key_as_str}"
+                    )));
+                }
+                unsafe { pyo3::ffi::Py_DECREF(value) };
+            }
+            items.push((key_as_str, value));
+        }
+
+        if unlikely(self.opts & STRUCT_AS_ARRAY != 0) {
+            let mut seq = serializer.serialize_seq(Some(items.len()))?;
+            for (_, value) in items.iter() {
+                seq.serialize_element(&PyObject::new(*value, self.state, self.opts, self.default))?;
+            }
+            return seq.end();
+        }
+
+        let mut map = serializer.serialize_map(Some(items.len()))?;
+        for (key, value) in items.iter() {
+            let pyvalue = PyObject::new(*value, self.state, self.opts, self.default);
+            map.serialize_key(key).unwrap();
+            map.serialize_value(&pyvalue)?
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::*;
+use crate::state::State;
+use serde::ser::{Serialize, Serializer};
+use serde_bytes::Bytes;
+
+/// Append `ptr` to `buffer` in two's complement big-endian representation,
+/// using the smallest number of bytes.
+pub fn extend_big_int(ptr: *mut pyo3::ffi::PyObject, state: *mut State, buffer: &mut Vec<u8>) {
+    let bit_length = unsafe {
+        let value = pyobject_call_method_no_args(ptr, (*state).bit_length_str);
+        let bit_length = pyo3::ffi::PyLong_AsSsize_t(value);
+        pyo3::ffi::Py_DECREF(value);
+        bit_length
+    };
+    let start = buffer.len();
+    buffer.resize(start + bit_length as usize / 8 + 1, 0);
+    unsafe { pylong_to_be_bytes(ptr, &mut buffer[start..]) };
+}
+
+pub struct BigInt {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    tag: i8,
+}
+
+impl BigInt {
+    pub fn new(ptr: *mut pyo3::ffi::PyObject, state: *mut State, tag: i8) -> Self {
+        BigInt {
+            ptr: ptr,
+            state: state,
+            tag: tag,
+        }
+    }
+}
+
+impl Serialize for BigInt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buffer = Vec::new();
+        extend_big_int(self.ptr, self.state, &mut buffer);
+        serializer.serialize_newtype_variant("", self.tag as u32, "", Bytes::new(&buffer))
+    }
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::ptr::NonNull;
+#[cfg(Py_GIL_DISABLED)]
+use std::sync::Mutex;
+
+struct CachedType {
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    value: *mut pyo3::ffi::PyObject,
+}
+
+unsafe impl Send for CachedType {}
+unsafe impl Sync for CachedType {}
+
+impl CachedType {
+    fn new(ob_type: *mut pyo3::ffi::PyTypeObject, value: *mut pyo3::ffi::PyObject) -> CachedType {
+        unsafe { pyo3::ffi::Py_INCREF(ob_type.cast::<pyo3::ffi::PyObject>()) };
+        CachedType {
+            ob_type: ob_type,
+            value: value,
+        }
+    }
+
+    fn get(&self) -> NonNull<pyo3::ffi::PyObject> {
+        unsafe {
+            pyo3::ffi::Py_INCREF(self.value);
+            NonNull::new_unchecked(self.value)
+        }
+    }
+}
+
+impl Drop for CachedType {
+    fn drop(&mut self) {
+        unsafe {
+            pyo3::ffi::Py_DECREF(self.value);
+            pyo3::ffi::Py_DECREF(self.ob_type.cast::<pyo3::ffi::PyObject>());
+        }
+    }
+}
+
+/// A direct-mapped cache of a Python object computed from a type, such as
+/// the field names of a namedtuple class. A reference to the type is kept
+/// while it is cached, so that its address cannot be reused by another type.
+pub struct TypeMap<const C: usize> {
+    #[cfg(Py_GIL_DISABLED)]
+    entries: Mutex<Vec<Option<CachedType>>>,
+    #[cfg(not(Py_GIL_DISABLED))]
+    entries: Vec<Option<CachedType>>,
+}
+
+impl<const C: usize> TypeMap<C> {
+    pub fn new() -> Self {
+        let mut entries = Vec::with_capacity(C);
+        for _ in 0..C {
+            entries.push(None);
+        }
+        TypeMap {
+            #[cfg(Py_GIL_DISABLED)]
+            entries: Mutex::new(entries),
+            #[cfg(not(Py_GIL_DISABLED))]
+            entries: entries,
+        }
+    }
+
+    /// Return a new reference to the value cached for `ob_type`, computing
+    /// it with `f` if it is missing. `f` returns a new reference, or `None`
+    /// if the value cannot be computed, in which case nothing is cached.
+    pub fn get<F>(
+        &mut self,
+        ob_type: *mut pyo3::ffi::PyTypeObject,
+        f: F,
+    ) -> Option<NonNull<pyo3::ffi::PyObject>>
+    where
+        F: FnOnce() -> Option<NonNull<pyo3::ffi::PyObject>>,
+    {
+        let index = (ob_type as usize >> 4) % C;
+        {
+            #[cfg(Py_GIL_DISABLED)]
+            let entries = self.entries.lock().unwrap();
+            #[cfg(not(Py_GIL_DISABLED))]
+            let entries = &self.entries;
+            if let Some(entry) = &entries[index] {
+                if entry.ob_type == ob_type {
+                    return Some(entry.get());
+                }
+            }
+        }
+        // The lock is not held while computing the value, since it can run
+        // arbitrary Python code.
+        let value = f()?;
+        unsafe { pyo3::ffi::Py_INCREF(value.as_ptr()) };
+        let entry = Some(CachedType::new(ob_type, value.as_ptr()));
+        let evicted = {
+            #[cfg(Py_GIL_DISABLED)]
+            let mut entries = self.entries.lock().unwrap();
+            #[cfg(not(Py_GIL_DISABLED))]
+            let entries = &mut self.entries;
+            std::mem::replace(&mut entries[index], entry)
+        };
+        drop(evicted);
+        Some(value)
+    }
+
+    /// Remove the value cached for `ob_type`, if any.
+    pub fn invalidate(&mut self, ob_type: *mut pyo3::ffi::PyTypeObject) {
+        let index = (ob_type as usize >> 4) % C;
+        let evicted = {
+            #[cfg(Py_GIL_DISABLED)]
+            let mut entries = self.entries.lock().unwrap();
+            #[cfg(not(Py_GIL_DISABLED))]
+            let entries = &mut self.entries;
+            match &entries[index] {
+                Some(entry) if entry.ob_type == ob_type => entries[index].take(),
+                _ => None,
+            }
+        };
+        drop(evicted);
+    }
+}
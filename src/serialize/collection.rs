@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::default::DefaultHook;
+use crate::serialize::dict::*;
+use crate::serialize::list::*;
+use crate::serialize::tuple::*;
+use crate::state::State;
+use crate::util::unlikely;
+
+use serde::ser::{Serialize, Serializer};
+
+#[inline(always)]
+pub fn is_mapping(ob_type: *mut pyo3::ffi::PyTypeObject) -> bool {
+    unsafe { pyo3::ffi::PyType_HasFeature(ob_type, pyo3::ffi::Py_TPFLAGS_MAPPING) != 0 }
+}
+
+#[inline(always)]
+pub fn is_sequence(ob_type: *mut pyo3::ffi::PyTypeObject) -> bool {
+    unsafe { pyo3::ffi::PyType_HasFeature(ob_type, pyo3::ffi::Py_TPFLAGS_SEQUENCE) != 0 }
+}
+
+/// An instance of a type registered as `collections.abc.Mapping`. The items
+/// are copied to a dict, so that the keys are serialized like the keys of
+/// a dict.
+pub struct Mapping<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> Mapping<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        Mapping {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Serialize for Mapping<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let items = unsafe { pyo3::ffi::PyMapping_Items(self.ptr) };
+        if unlikely(items.is_null()) {
+            return Err(serde::ser::Error::custom("Mapping items could not be read"));
+        }
+        let dict = unsafe { pyo3::ffi::PyDict_New() };
+        let res = unsafe { pyo3::ffi::PyDict_MergeFromSeq2(dict, items, 1) };
+        unsafe { pyo3::ffi::Py_DECREF(items) };
+        if unlikely(res == -1) {
+            unsafe { pyo3::ffi::Py_DECREF(dict) };
+            return Err(serde::ser::Error::custom("Mapping items could not be read"));
+        }
+        let res = Dict::new(dict, self.state, self.opts, self.default).serialize(serializer);
+        unsafe { pyo3::ffi::Py_DECREF(dict) };
+        res
+    }
+}
+
+/// An instance of a type registered as `collections.abc.Sequence`.
+pub struct Sequence<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> Sequence<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        Sequence {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Serialize for Sequence<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let seq = unsafe { pyo3::ffi::PySequence_Fast(self.ptr, c"".as_ptr()) };
+        if unlikely(seq.is_null()) {
+            return Err(serde::ser::Error::custom(
+                "Sequence items could not be read",
+            ));
+        }
+        let res = if unsafe { pyo3::ffi::PyList_Check(seq) } != 0 {
+            List::new(seq, self.state, self.opts, self.default).serialize(serializer)
+        } else {
+            Tuple::new(seq, self.state, self.opts, self.default).serialize(serializer)
+        };
+        unsafe { pyo3::ffi::Py_DECREF(seq) };
+        res
+    }
+}
@@ -1,15 +1,18 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::ffi::*;
+use crate::msgpack;
 use crate::opt::*;
 use crate::serialize::default::DefaultHook;
+use crate::serialize::fragment::EncodedItem;
 use crate::serialize::serializer::*;
 use crate::state::State;
 use crate::util::unlikely;
 
-use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
 use smallvec::SmallVec;
+use std::ptr::NonNull;
 
 #[inline]
 fn has_slots(ob_type: *mut pyo3::ffi::PyTypeObject, state: *mut State) -> bool {
@@ -57,22 +60,282 @@ fn is_pseudo_field(field: *mut pyo3::ffi::PyObject, state: *mut State) -> bool {
     field_type.cast::<pyo3::ffi::PyTypeObject>() != unsafe { (*state).dataclass_field_type }
 }
 
+#[inline]
+fn is_private(attr: *mut pyo3::ffi::PyObject) -> bool {
+    unsafe {
+        pyo3::ffi::PyUnicode_GetLength(attr) > 0
+            && pyo3::ffi::PyUnicode_ReadChar(attr, 0) == '_' as u32
+    }
+}
+
+/// Return the default value of a field, or `dataclasses.MISSING` if the
+/// field has no default value. Default factories are not called, so that
+/// serializing does not run user code with side effects.
+unsafe fn load_default(
+    field: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    let default = pyo3::ffi::PyObject_GetAttr(field, (*state).default_str);
+    if default.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return pyo3::ffi::Py_NewRef((*state).dataclass_missing);
+    }
+    default
+}
+
+/// Return whether a field is a parameter of the generated `__init__`.
+unsafe fn load_init(field: *mut pyo3::ffi::PyObject, state: *mut State) -> bool {
+    let init = pyo3::ffi::PyObject_GetAttr(field, (*state).init_str);
+    if init.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return true;
+    }
+    pyo3::ffi::Py_DECREF(init);
+    init != pyo3::ffi::Py_False()
+}
+
+/// Return the name of a field set in its `msgpack` metadata, or null if
+/// not set, and whether the field is skipped.
+unsafe fn load_metadata(
+    field: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> (*mut pyo3::ffi::PyObject, bool) {
+    let mut name = std::ptr::null_mut();
+    let mut skip = false;
+    let metadata = pyo3::ffi::PyObject_GetAttr(field, (*state).metadata_str);
+    if metadata.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return (name, skip);
+    }
+    let options = pyo3::ffi::PyObject_GetItem(metadata, (*state).msgpack_key_str);
+    pyo3::ffi::Py_DECREF(metadata);
+    if options.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return (name, skip);
+    }
+    let value = pyo3::ffi::PyObject_GetItem(options, (*state).name_str);
+    if value.is_null() {
+        pyo3::ffi::PyErr_Clear();
+    } else if pyo3::ffi::PyUnicode_Check(value) != 0 {
+        name = value;
+    } else {
+        pyo3::ffi::Py_DECREF(value);
+    }
+    let value = pyo3::ffi::PyObject_GetItem(options, (*state).skip_str);
+    if value.is_null() {
+        pyo3::ffi::PyErr_Clear();
+    } else {
+        skip = pyo3::ffi::PyObject_IsTrue(value) == 1;
+        pyo3::ffi::Py_DECREF(value);
+    }
+    pyo3::ffi::Py_DECREF(options);
+    (name, skip)
+}
+
+/// Return the name of a field serialized as a msgpack str, or `None` if the
+/// name is not valid UTF-8.
+unsafe fn encode_name(name: *mut pyo3::ffi::PyObject) -> *mut pyo3::ffi::PyObject {
+    match unicode_to_str(name) {
+        Ok(name) => {
+            let mut buf: Vec<u8> = Vec::with_capacity(name.len() + 5);
+            msgpack::write_str(&mut buf, name).unwrap();
+            pyo3::ffi::PyBytes_FromStringAndSize(buf.as_ptr().cast(), buf.len() as isize)
+        }
+        Err(_) => pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()),
+    }
+}
+
+/// Return the serialization plan of a dataclass as a `(version, slots,
+/// fields)` tuple, where `version` is the version tag of the class when the
+/// plan was built, `slots` is whether the class defines `__slots__`, and
+/// `fields` is a tuple of `(attr, name, default, init, key)` tuples, `key`
+/// being the name serialized as a msgpack str. Pseudo-fields, private fields
+/// and skipped fields are excluded. `None` is returned if the class has no
+/// valid `__dataclass_fields__`.
+fn load_plan(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let fields = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).dataclass_fields_str);
+        if fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        if pyo3::ffi::PyDict_Check(fields) == 0 {
+            pyo3::ffi::Py_DECREF(fields);
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        // The version tag is valid after an attribute lookup on the class,
+        // and changes whenever the class is modified.
+        let version = version_tag(ob_type);
+        let entries = pyo3::ffi::PyList_New(0);
+        for (attr, field) in PyDictIter::from_pyobject(fields) {
+            let attr = attr.as_ptr();
+            let field = field.as_ptr();
+            if pyo3::ffi::PyUnicode_Check(attr) == 0 || is_pseudo_field(field, state) {
+                continue;
+            }
+            let (mut name, skip) = load_metadata(field, state);
+            if skip {
+                pyo3::ffi::Py_XDECREF(name);
+                continue;
+            }
+            if name.is_null() {
+                if is_private(attr) {
+                    continue;
+                }
+                name = pyo3::ffi::Py_NewRef(attr);
+            }
+            let init = if load_init(field, state) {
+                pyo3::ffi::Py_True()
+            } else {
+                pyo3::ffi::Py_False()
+            };
+            let entry = pyo3::ffi::PyTuple_New(5);
+            pytuple_set_item(entry, 0, pyo3::ffi::Py_NewRef(attr));
+            pytuple_set_item(entry, 1, name);
+            pytuple_set_item(entry, 2, load_default(field, state));
+            pytuple_set_item(entry, 3, pyo3::ffi::Py_NewRef(init));
+            pytuple_set_item(entry, 4, encode_name(name));
+            pyo3::ffi::PyList_Append(entries, entry);
+            pyo3::ffi::Py_DECREF(entry);
+        }
+        pyo3::ffi::Py_DECREF(fields);
+        let slots = if has_slots(ob_type, state) {
+            pyo3::ffi::Py_True()
+        } else {
+            pyo3::ffi::Py_False()
+        };
+        let plan = pyo3::ffi::PyTuple_New(3);
+        pytuple_set_item(plan, 0, pyo3::ffi::PyLong_FromUnsignedLong(version.into()));
+        pytuple_set_item(plan, 1, pyo3::ffi::Py_NewRef(slots));
+        pytuple_set_item(plan, 2, pyo3::ffi::PyList_AsTuple(entries));
+        pyo3::ffi::Py_DECREF(entries);
+        NonNull::new(plan)
+    }
+}
+
+/// Return the version tag of a class, which is read atomically since it is
+/// assigned by attribute lookups without a lock in free-threaded builds.
+#[inline]
+fn version_tag(ob_type: *mut pyo3::ffi::PyTypeObject) -> u32 {
+    #[cfg(Py_GIL_DISABLED)]
+    unsafe {
+        std::sync::atomic::AtomicU32::from_ptr(&raw mut (*ob_type).tp_version_tag)
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+    #[cfg(not(Py_GIL_DISABLED))]
+    unsafe {
+        (*ob_type).tp_version_tag
+    }
+}
+
+/// Return a new reference to the serialization plan of a dataclass, or
+/// `None` if the class has no valid `__dataclass_fields__`. The plan is
+/// cached per class and rebuilt if the class is modified. A plan built
+/// without a valid version tag, which happens if the version tags are
+/// exhausted, is kept since the modifications cannot be detected.
+fn dataclass_plan(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    let cache = unsafe { &mut (*state).dataclass_plans };
+    let mut plan = cache.get(ob_type, || load_plan(ob_type, state))?;
+    if plan.as_ptr() != unsafe { pyo3::ffi::Py_None() } {
+        let version =
+            unsafe { pyo3::ffi::PyLong_AsUnsignedLong(pytuple_get_item(plan.as_ptr(), 0)) } as u32;
+        if unlikely(version != 0 && version != version_tag(ob_type)) {
+            unsafe { pyo3::ffi::Py_DECREF(plan.as_ptr()) };
+            cache.invalidate(ob_type);
+            plan = cache.get(ob_type, || load_plan(ob_type, state))?;
+        }
+    }
+    if plan.as_ptr() == unsafe { pyo3::ffi::Py_None() } {
+        unsafe { pyo3::ffi::Py_DECREF(plan.as_ptr()) };
+        None
+    } else {
+        Some(plan)
+    }
+}
+
+/// Return a new reference to the fields of a dataclass to serialize, as a
+/// tuple of `(attr, name, default, init, key)` tuples, or `None` if the
+/// class has no valid `__dataclass_fields__`.
+pub fn dataclass_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    let plan = dataclass_plan(ob_type, state)?;
+    let fields = unsafe { pyo3::ffi::Py_NewRef(pytuple_get_item(plan.as_ptr(), 2)) };
+    unsafe { pyo3::ffi::Py_DECREF(plan.as_ptr()) };
+    NonNull::new(fields)
+}
+
+/// Return whether a field value is omitted by `OPT_OMIT_NONE` or
+/// `OPT_OMIT_DEFAULTS`. A value is equal to its default only if it is the
+/// default or has the same type, so that `False` is not omitted for a default
+/// of `0`.
+fn is_omitted(
+    value: *mut pyo3::ffi::PyObject,
+    entry: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+) -> bool {
+    if opts & OMIT_NONE != 0 && value == unsafe { pyo3::ffi::Py_None() } {
+        return true;
+    }
+    let default = unsafe { pytuple_get_item(entry, 2) };
+    if opts & OMIT_DEFAULTS != 0 && default != unsafe { (*state).dataclass_missing } {
+        if value == default {
+            return true;
+        }
+        if ob_type!(value) != ob_type!(default) {
+            return false;
+        }
+        match unsafe { pyo3::ffi::PyObject_RichCompareBool(value, default, pyo3::ffi::Py_EQ) } {
+            1 => return true,
+            -1 => unsafe { pyo3::ffi::PyErr_Clear() },
+            _ => {}
+        }
+    }
+    false
+}
+
 impl Serialize for Dataclass<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let fields =
-            unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).dataclass_fields_str) };
-        unsafe { pyo3::ffi::Py_DECREF(fields) };
-        let len = unsafe { pydict_size(fields) } as usize;
-        if unlikely(len == 0) {
-            return serializer.serialize_map(Some(0))?.end();
-        }
+        let plan = match dataclass_plan(ob_type!(self.ptr), self.state) {
+            Some(plan) => plan.as_ptr(),
+            None => {
+                return Err(serde::ser::Error::custom(
+                    "__dataclass_fields__ must be a dict",
+                ))
+            }
+        };
+        // The plan is owned until the object is serialized, since it may be
+        // evicted from the cache by nested objects.
+        let res = self.serialize_fields(serializer, plan);
+        unsafe { pyo3::ffi::Py_DECREF(plan) };
+        res
+    }
+}
 
+impl Dataclass<'_> {
+    fn serialize_fields<S>(
+        &self,
+        serializer: S,
+        plan: *mut pyo3::ffi::PyObject,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields = unsafe { pytuple_get_item(plan, 2) };
+        let len = unsafe { pyo3::ffi::Py_SIZE(fields) } as usize;
         let dict = {
-            let ob_type = ob_type!(self.ptr);
-            if has_slots(ob_type, self.state) {
+            if unsafe { pytuple_get_item(plan, 1) } == unsafe { pyo3::ffi::Py_True() } {
                 std::ptr::null_mut()
             } else {
                 let dict = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).dict_str) };
@@ -81,38 +344,70 @@ impl Serialize for Dataclass<'_> {
             }
         };
 
-        let mut items: SmallVec<[(&str, *mut pyo3::ffi::PyObject); 8]> =
+        if unlikely(self.opts & STRUCT_AS_ARRAY != 0) {
+            let mut seq = serializer.serialize_seq(Some(len))?;
+            for i in 0..len {
+                let entry = unsafe { pytuple_get_item(fields, i as isize) };
+                let value = self
+                    .field_value(dict, unsafe { pytuple_get_item(entry, 0) })
+                    .map_err(serde::ser::Error::custom)?;
+                seq.serialize_element(&PyObject::new(value, self.state, self.opts, self.default))?;
+            }
+            return seq.end();
+        }
+
+        if unlikely(len == 0) {
+            return serializer.serialize_map(Some(0))?.end();
+        }
+
+        let mut items: SmallVec<[(&[u8], *mut pyo3::ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
-        for (attr, field) in PyDictIter::from_pyobject(fields) {
-            let key_as_str = unicode_to_str(attr.as_ptr()).map_err(serde::ser::Error::custom)?;
-            if key_as_str.as_bytes()[0] == b'_' {
+        for i in 0..len {
+            let entry = unsafe { pytuple_get_item(fields, i as isize) };
+            let value = self
+                .field_value(dict, unsafe { pytuple_get_item(entry, 0) })
+                .map_err(serde::ser::Error::custom)?;
+            if unlikely(self.opts & (OMIT_NONE | OMIT_DEFAULTS) != 0)
+                && is_omitted(value, entry, self.state, self.opts)
+            {
                 continue;
             }
-
-            if unlikely(dict.is_null()) {
-                if !is_pseudo_field(field.as_ptr(), self.state) {
-                    let value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, attr.as_ptr()) };
-                    unsafe { pyo3::ffi::Py_DECREF(value) };
-                    items.push((key_as_str, value));
-                }
-            } else {
-                let value = unsafe { pyo3::ffi::PyDict_GetItem(dict, attr.as_ptr()) };
-                if !value.is_null() {
-                    items.push((key_as_str, value));
-                } else if !is_pseudo_field(field.as_ptr(), self.state) {
-                    let value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, attr.as_ptr()) };
-                    unsafe { pyo3::ffi::Py_DECREF(value) };
-                    items.push((key_as_str, value));
-                }
+            let key = unsafe { pytuple_get_item(entry, 4) };
+            if unlikely(key == unsafe { pyo3::ffi::Py_None() }) {
+                let name = unsafe { pytuple_get_item(entry, 1) };
+                unicode_to_str(name).map_err(serde::ser::Error::custom)?;
             }
+            items.push((unsafe { pybytes_as_bytes(key) }, value));
         }
 
         let mut map = serializer.serialize_map(Some(items.len()))?;
         for (key, value) in items.iter() {
             let pyvalue = PyObject::new(*value, self.state, self.opts, self.default);
-            map.serialize_key(key).unwrap();
+            map.serialize_key(&EncodedItem(key)).unwrap();
             map.serialize_value(&pyvalue)?
         }
         map.end()
     }
+
+    /// Return a borrowed reference to the value of a field, looked up in
+    /// `dict` if it is not null.
+    fn field_value(
+        &self,
+        dict: *mut pyo3::ffi::PyObject,
+        attr: *mut pyo3::ffi::PyObject,
+    ) -> Result<*mut pyo3::ffi::PyObject, String> {
+        if !dict.is_null() {
+            let value = unsafe { pyo3::ffi::PyDict_GetItem(dict, attr) };
+            if !value.is_null() {
+                return Ok(value);
+            }
+        }
+        let value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, attr) };
+        if unlikely(value.is_null()) {
+            let attr = unicode_to_str(attr).unwrap_or_default();
+            return Err(format!("dataclass field is not set: {attr}"));
+        }
+        unsafe { pyo3::ffi::Py_DECREF(value) };
+        Ok(value)
+    }
 }
@@ -1,8 +1,11 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::ffi::*;
+use crate::msgpack;
 use crate::opt::*;
-use crate::serialize::datetimelike::{DateLike, DateTimeLike, TimeLike};
+use crate::serialize::datetimelike::{
+    serialize_duration, DateLike, DateTimeLike, DurationLike, TimeLike,
+};
 use crate::state::State;
 use crate::util::unlikely;
 use serde::ser::{Serialize, Serializer};
@@ -157,8 +160,10 @@ unsafe fn utcoffset(
 
 pub struct DateTime {
     ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
     opts: Opt,
     offset: Option<i32>,
+    tag: i8,
 }
 
 impl DateTime {
@@ -166,14 +171,56 @@ impl DateTime {
         ptr: *mut pyo3::ffi::PyObject,
         state: *mut State,
         opts: Opt,
+        tag: i8,
     ) -> Result<Self, DateTimeError> {
         let offset = unsafe { utcoffset(ptr, state)? };
         Ok(DateTime {
             ptr: ptr,
+            state: state,
             opts: opts,
             offset: offset,
+            tag: tag,
         })
     }
+
+    /// Return a new reference to the key of the tzinfo if it is a
+    /// `zoneinfo.ZoneInfo` instance, or null.
+    fn zone_key(&self) -> *mut pyo3::ffi::PyObject {
+        unsafe {
+            let tzinfo = pyo3::ffi::PyDateTime_DATE_GET_TZINFO(self.ptr);
+            if tzinfo == pyo3::ffi::Py_None()
+                || pyo3::ffi::PyObject_TypeCheck(tzinfo, (*self.state).get_zoneinfo_type()) == 0
+            {
+                return std::ptr::null_mut();
+            }
+            // a subclass can compute the key, so a reference is kept until
+            // its bytes are copied
+            let key = pyo3::ffi::PyObject_GetAttr(tzinfo, (*self.state).key_str);
+            if unlikely(key.is_null()) {
+                pyo3::ffi::PyErr_Clear();
+                return std::ptr::null_mut();
+            }
+            if pyo3::ffi::PyUnicode_Check(key) == 0 {
+                pyo3::ffi::Py_DECREF(key);
+                return std::ptr::null_mut();
+            }
+            key
+        }
+    }
+
+    fn write_tz_ext(&self, buffer: &mut Vec<u8>) {
+        let datetime = self.to_utc_datetime();
+        buffer.extend_from_slice(&datetime.timestamp().to_be_bytes());
+        buffer.extend_from_slice(&datetime.timestamp_subsec_nanos().to_be_bytes());
+        buffer.extend_from_slice(&self.offset.unwrap_or_default().to_be_bytes());
+        let key = self.zone_key();
+        if !key.is_null() {
+            if let Ok(key) = unicode_to_str(key) {
+                buffer.extend_from_slice(key.as_bytes());
+            }
+            unsafe { pyo3::ffi::Py_DECREF(key) };
+        }
+    }
 }
 
 impl DateLike for DateTime {
@@ -238,14 +285,28 @@ impl Serialize for DateTime {
     where
         S: Serializer,
     {
+        let has_offset = self.offset().is_some() || self.opts & NAIVE_UTC != 0;
+        if self.opts & DATETIME_AS_TZ_EXT != 0 && has_offset {
+            let mut buffer: Vec<u8> = Vec::with_capacity(48);
+            self.write_tz_ext(&mut buffer);
+            return serializer.serialize_newtype_variant(
+                "",
+                self.tag as u32,
+                "",
+                Bytes::new(&buffer),
+            );
+        }
         let mut cursor = std::io::Cursor::new([0u8; 32]);
-        if self.opts & DATETIME_AS_TIMESTAMP_EXT != 0
-            && (self.offset().is_some() || self.opts & NAIVE_UTC != 0)
-        {
+        if self.opts & DATETIME_AS_TIMESTAMP_EXT != 0 && has_offset {
             DateTimeLike::write_timestamp(self, &mut cursor).unwrap();
             let len = cursor.position() as usize;
             let timestamp = &cursor.get_ref()[0..len];
-            serializer.serialize_newtype_variant("", 128, "", Bytes::new(timestamp))
+            serializer.serialize_newtype_variant(
+                "",
+                msgpack::ext_variant_index(-1),
+                "",
+                Bytes::new(timestamp),
+            )
         } else {
             DateTimeLike::write_rfc3339(self, &mut cursor, self.opts).unwrap();
             let len = cursor.position() as usize;
@@ -254,3 +315,43 @@ impl Serialize for DateTime {
         }
     }
 }
+
+pub struct Timedelta {
+    ptr: *mut pyo3::ffi::PyObject,
+    opts: Opt,
+    tag: i8,
+}
+
+impl Timedelta {
+    pub fn new(ptr: *mut pyo3::ffi::PyObject, opts: Opt, tag: i8) -> Self {
+        Timedelta {
+            ptr: ptr,
+            opts: opts,
+            tag: tag,
+        }
+    }
+}
+
+impl DurationLike for Timedelta {
+    fn days(&self) -> i32 {
+        unsafe { pyo3::ffi::PyDateTime_DELTA_GET_DAYS(self.ptr) as i32 }
+    }
+
+    fn seconds(&self) -> i32 {
+        unsafe { pyo3::ffi::PyDateTime_DELTA_GET_SECONDS(self.ptr) as i32 }
+    }
+
+    fn microseconds(&self) -> i32 {
+        unsafe { pyo3::ffi::PyDateTime_DELTA_GET_MICROSECONDS(self.ptr) as i32 }
+    }
+}
+
+impl Serialize for Timedelta {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_duration(self, self.opts, self.tag, serializer)
+    }
+}
@@ -11,7 +11,7 @@ where
 {
     let mut itoa_buf = itoa::Buffer::new();
     let formatted = itoa_buf.format(value);
-    for _ in 0..width - formatted.len() {
+    for _ in formatted.len()..width {
         writer.write_all(b"0")?;
     }
     let len = writer.write(formatted.as_bytes())?;
@@ -120,6 +120,102 @@ pub trait DateTimeLike: DateLike + TimeLike {
     }
 }
 
+pub trait DurationLike {
+    fn days(&self) -> i32;
+    fn seconds(&self) -> i32;
+    fn microseconds(&self) -> i32;
+
+    fn total_microseconds(&self) -> i128 {
+        (i128::from(self.days()) * 86400 + i128::from(self.seconds())) * 1_000_000
+            + i128::from(self.microseconds())
+    }
+
+    /// Return the total number of seconds, correctly rounded like
+    /// `datetime.timedelta.total_seconds()`.
+    fn total_seconds(&self) -> f64 {
+        let value = self.total_microseconds();
+        if value.unsigned_abs() < 1 << f64::MANTISSA_DIGITS {
+            // both operands are exact, so the quotient is correctly rounded
+            value as f64 / 1e6
+        } else {
+            // the true division of Python integers is correctly rounded
+            let digits = std::ffi::CString::new(value.to_string()).unwrap();
+            unsafe {
+                let numerator =
+                    pyo3::ffi::PyLong_FromString(digits.as_ptr(), std::ptr::null_mut(), 10);
+                let denominator = pyo3::ffi::PyLong_FromLong(1_000_000);
+                let quotient = pyo3::ffi::PyNumber_TrueDivide(numerator, denominator);
+                let res = pyo3::ffi::PyFloat_AS_DOUBLE(quotient);
+                pyo3::ffi::Py_DECREF(quotient);
+                pyo3::ffi::Py_DECREF(denominator);
+                pyo3::ffi::Py_DECREF(numerator);
+                res
+            }
+        }
+    }
+
+    fn write_iso8601<W>(&self, writer: &mut W) -> Result<(), std::io::Error>
+    where
+        W: std::io::Write,
+    {
+        let mut value = self.total_microseconds();
+        if value < 0 {
+            writer.write_all(b"-")?;
+            value = -value;
+        }
+        writer.write_all(b"P")?;
+        let microsecond = (value % 1_000_000) as i32;
+        value /= 1_000_000;
+        let second = (value % 60) as i32;
+        value /= 60;
+        let minute = (value % 60) as i32;
+        value /= 60;
+        let hour = (value % 24) as i32;
+        let day = (value / 24) as i32;
+        if day != 0 {
+            write_integer(writer, day, 1)?;
+            writer.write_all(b"D")?;
+        }
+        if hour != 0 || minute != 0 || second != 0 || microsecond != 0 || day == 0 {
+            writer.write_all(b"T")?;
+            if hour != 0 {
+                write_integer(writer, hour, 1)?;
+                writer.write_all(b"H")?;
+            }
+            if minute != 0 {
+                write_integer(writer, minute, 1)?;
+                writer.write_all(b"M")?;
+            }
+            if second != 0 || microsecond != 0 || (hour == 0 && minute == 0) {
+                write_integer(writer, second, 1)?;
+                if microsecond != 0 {
+                    let mut digits = 6;
+                    let mut fraction = microsecond;
+                    while fraction % 10 == 0 {
+                        fraction /= 10;
+                        digits -= 1;
+                    }
+                    writer.write_all(b".")?;
+                    write_integer(writer, fraction, digits)?;
+                }
+                writer.write_all(b"S")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_timestamp<W>(&self, writer: &mut W) -> Result<(), std::io::Error>
+    where
+        W: std::io::Write,
+    {
+        msgpack::write_timestamp_value(
+            writer,
+            i64::from(self.days()) * 86400 + i64::from(self.seconds()),
+            self.microseconds() as u32 * 1000,
+        )
+    }
+}
+
 pub struct NaiveDateTime {
     pub dt: chrono::NaiveDateTime,
     pub opts: Opt,
@@ -179,3 +275,45 @@ impl Serialize for NaiveDateTime {
         serializer.serialize_str(value)
     }
 }
+
+/// Serialize a duration as selected by the `OPT_TIMEDELTA_*` options.
+pub fn serialize_duration<D, S>(
+    value: &D,
+    opts: Opt,
+    tag: i8,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    D: DurationLike,
+    S: Serializer,
+{
+    let encoding = opts & (TIMEDELTA_AS_EXT | TIMEDELTA_AS_MICROSECONDS | TIMEDELTA_AS_SECONDS);
+    if encoding == TIMEDELTA_AS_EXT {
+        let mut cursor = std::io::Cursor::new([0u8; 12]);
+        value.write_timestamp(&mut cursor).unwrap();
+        let len = cursor.position() as usize;
+        let timestamp = &cursor.get_ref()[0..len];
+        serializer.serialize_newtype_variant("", tag as u32, "", serde_bytes::Bytes::new(timestamp))
+    } else if encoding == TIMEDELTA_AS_MICROSECONDS {
+        let value = value.total_microseconds();
+        if let Ok(value) = i64::try_from(value) {
+            serializer.serialize_i64(value)
+        } else if let Ok(value) = u64::try_from(value) {
+            serializer.serialize_u64(value)
+        } else {
+            Err(serde::ser::Error::custom("Integer exceeds 64-bit range"))
+        }
+    } else if encoding == TIMEDELTA_AS_SECONDS {
+        serializer.serialize_f64(value.total_seconds())
+    } else if encoding == 0 {
+        let mut cursor = std::io::Cursor::new([0u8; 48]);
+        value.write_iso8601(&mut cursor).unwrap();
+        let len = cursor.position() as usize;
+        let value = unsafe { std::str::from_utf8_unchecked(&cursor.get_ref()[0..len]) };
+        serializer.serialize_str(value)
+    } else {
+        Err(serde::ser::Error::custom(
+            "OPT_TIMEDELTA_AS_EXT, OPT_TIMEDELTA_AS_MICROSECONDS and OPT_TIMEDELTA_AS_SECONDS are mutually exclusive",
+        ))
+    }
+}
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::*;
+use crate::opt::*;
+use crate::serialize::bigint::extend_big_int;
+use crate::serialize::str::Str;
+use crate::state::State;
+use crate::util::unlikely;
+use serde::ser::{Serialize, Serializer};
+use serde_bytes::Bytes;
+use std::os::raw::c_char;
+
+pub struct Decimal {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    tag: i8,
+}
+
+impl Decimal {
+    pub fn new(ptr: *mut pyo3::ffi::PyObject, state: *mut State, opts: Opt, tag: i8) -> Self {
+        Decimal {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            tag: tag,
+        }
+    }
+
+    /// Write the scale as a 32-bit big-endian integer followed by the unscaled
+    /// value in two's complement big-endian representation, so that the value
+    /// is `unscaled * 10 ** -scale`.
+    fn write_ext(&self, buffer: &mut Vec<u8>) -> Result<(), &'static str> {
+        unsafe {
+            let tuple = pyobject_call_method_no_args(self.ptr, (*self.state).as_tuple_str);
+            let sign = pytuple_get_item(tuple, 0);
+            let digits = pytuple_get_item(tuple, 1);
+            let exponent = pytuple_get_item(tuple, 2);
+            if unlikely(pyo3::ffi::PyLong_Check(exponent) == 0) {
+                pyo3::ffi::Py_DECREF(tuple);
+                return Err("Decimal is not finite");
+            }
+            // the exponent of a finite decimal fits in an i64
+            let scale = match i32::try_from(-pyo3::ffi::PyLong_AsLongLong(exponent)) {
+                Ok(val) => val,
+                Err(_) => {
+                    pyo3::ffi::Py_DECREF(tuple);
+                    return Err("Decimal exponent exceeds 32-bit range");
+                }
+            };
+
+            let num_digits = pyo3::ffi::PyTuple_Size(digits);
+            let mut unscaled_str: Vec<u8> = Vec::with_capacity(num_digits as usize + 2);
+            if pyo3::ffi::PyLong_AsLong(sign) != 0 {
+                unscaled_str.push(b'-');
+            }
+            for i in 0..num_digits {
+                let digit = pyo3::ffi::PyLong_AsLong(pytuple_get_item(digits, i));
+                unscaled_str.push(b'0' + digit as u8);
+            }
+            unscaled_str.push(0);
+            pyo3::ffi::Py_DECREF(tuple);
+
+            let unscaled = pyo3::ffi::PyLong_FromString(
+                unscaled_str.as_ptr().cast::<c_char>(),
+                std::ptr::null_mut(),
+                10,
+            );
+            buffer.extend_from_slice(&scale.to_be_bytes());
+            extend_big_int(unscaled, self.state, buffer);
+            pyo3::ffi::Py_DECREF(unscaled);
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.opts & DECIMAL_AS_EXT != 0 {
+            let mut buffer = Vec::new();
+            match self.write_ext(&mut buffer) {
+                Ok(()) => serializer.serialize_newtype_variant(
+                    "",
+                    self.tag as u32,
+                    "",
+                    Bytes::new(&buffer),
+                ),
+                Err(err) => Err(serde::ser::Error::custom(err)),
+            }
+        } else {
+            let value = unsafe { pyo3::ffi::PyObject_Str(self.ptr) };
+            let res = Str::new(value, self.opts).serialize(serializer);
+            unsafe { pyo3::ffi::Py_DECREF(value) };
+            res
+        }
+    }
+}
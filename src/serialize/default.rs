@@ -2,14 +2,17 @@
 
 use crate::ffi::*;
 use crate::msgpack::RECURSION_LIMIT;
+use crate::opt::ExtTags;
+use crate::serialize::cache::TypeMap;
 use crate::util::unlikely;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::CStr;
 use std::ptr::NonNull;
 
 pub enum Error {
     InvalidType(*mut pyo3::ffi::PyObject),
+    MethodFailed(*mut pyo3::ffi::PyObject),
     RecursionLimitReached,
 }
 
@@ -21,21 +24,122 @@ impl std::fmt::Display for Error {
                 let name = unsafe { CStr::from_ptr((*ob_type!(ptr)).tp_name).to_string_lossy() };
                 write!(f, "Type is not msgpack serializable: {name}")
             }
+            Error::MethodFailed(ptr) => {
+                let name = unsafe { CStr::from_ptr((*ob_type!(ptr)).tp_name).to_string_lossy() };
+                write!(f, "__msgpack__ method failed: {name}")
+            }
             Error::RecursionLimitReached => f.write_str("Recursion limit for default hook reached"),
         }
     }
 }
 
+/// Return the value mapped to the first class in the MRO of `ob_type`, or
+/// `None` if no class is mapped.
+fn load_mro_value(
+    mapping: *mut pyo3::ffi::PyObject,
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let mro = (*ob_type).tp_mro;
+        if !mro.is_null() && pyo3::ffi::PyTuple_Check(mro) != 0 {
+            for i in 0..pyo3::ffi::Py_SIZE(mro) {
+                let base = pytuple_get_item(mro, i);
+                let value = pyo3::ffi::PyDict_GetItemWithError(mapping, base);
+                if !value.is_null() {
+                    return NonNull::new(pyo3::ffi::Py_NewRef(value));
+                }
+                if unlikely(!pyo3::ffi::PyErr_Occurred().is_null()) {
+                    pyo3::ffi::PyErr_Clear();
+                }
+            }
+        }
+        NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()))
+    }
+}
+
+/// A `dict` mapping classes to values, whose lookups are cached per type.
+struct TypeRegistry {
+    mapping: NonNull<pyo3::ffi::PyObject>,
+    cache: RefCell<TypeMap<16>>,
+}
+
+impl TypeRegistry {
+    fn new(mapping: NonNull<pyo3::ffi::PyObject>) -> Self {
+        TypeRegistry {
+            mapping: mapping,
+            cache: RefCell::new(TypeMap::new()),
+        }
+    }
+
+    /// Return a new reference to the value mapped to `ob_type` or to one of
+    /// its base classes, or `None` if no class is mapped or it is mapped to
+    /// `None`.
+    fn get(&self, ob_type: *mut pyo3::ffi::PyTypeObject) -> Option<NonNull<pyo3::ffi::PyObject>> {
+        let value = self
+            .cache
+            .borrow_mut()
+            .get(ob_type, || load_mro_value(self.mapping.as_ptr(), ob_type))?;
+        if value.as_ptr() == unsafe { pyo3::ffi::Py_None() } {
+            unsafe { pyo3::ffi::Py_DECREF(value.as_ptr()) };
+            None
+        } else {
+            Some(value)
+        }
+    }
+}
+
 pub struct DefaultHook {
     pub inner: Option<NonNull<pyo3::ffi::PyObject>>,
+    pub ext_tags: ExtTags,
+    callables: Option<TypeRegistry>,
+    ext_types: Option<TypeRegistry>,
     recursion: Cell<u8>,
+    nested: Cell<u8>,
 }
 
 impl DefaultHook {
-    pub fn new(default: Option<NonNull<pyo3::ffi::PyObject>>) -> Self {
+    pub fn new(
+        default: Option<NonNull<pyo3::ffi::PyObject>>,
+        ext_types: Option<NonNull<pyo3::ffi::PyObject>>,
+        ext_tags: ExtTags,
+    ) -> Self {
+        let callables = match default {
+            Some(mapping) if unsafe { pyo3::ffi::PyDict_Check(mapping.as_ptr()) } != 0 => {
+                Some(TypeRegistry::new(mapping))
+            }
+            _ => None,
+        };
         DefaultHook {
             inner: default,
+            ext_tags: ext_tags,
+            callables: callables,
+            ext_types: ext_types.map(TypeRegistry::new),
             recursion: Cell::new(0),
+            nested: Cell::new(0),
+        }
+    }
+
+    /// Return a new reference to the `(tag, encoder)` tuple registered in
+    /// `ext_types` for `ob_type`, or `None` if the type is not registered.
+    #[inline]
+    pub fn ext_type(
+        &self,
+        ob_type: *mut pyo3::ffi::PyTypeObject,
+    ) -> Option<NonNull<pyo3::ffi::PyObject>> {
+        self.ext_types.as_ref()?.get(ob_type)
+    }
+
+    /// Return a new reference to the callable that serializes instances of
+    /// `ob_type`. If `default` is a mapping, the callable is looked up along
+    /// the MRO of the type and cached per type.
+    fn callable(
+        &self,
+        ob_type: *mut pyo3::ffi::PyTypeObject,
+    ) -> Option<NonNull<pyo3::ffi::PyObject>> {
+        let default = self.inner?;
+        match &self.callables {
+            Some(callables) => callables.get(ob_type),
+            None => NonNull::new(unsafe { pyo3::ffi::Py_NewRef(default.as_ptr()) }),
         }
     }
 
@@ -43,26 +147,63 @@ impl DefaultHook {
         &self,
         ptr: *mut pyo3::ffi::PyObject,
     ) -> Result<*mut pyo3::ffi::PyObject, Error> {
-        match self.inner {
+        match self.callable(ob_type!(ptr)) {
             Some(callable) => {
-                let recursion = self.recursion.get();
-                if unlikely(recursion == RECURSION_LIMIT) {
-                    return Err(Error::RecursionLimitReached);
-                }
-                self.recursion.set(recursion + 1);
-                let default_obj = unsafe { pyobject_call_one_arg(callable.as_ptr(), ptr) };
-                if unlikely(default_obj.is_null()) {
-                    Err(Error::InvalidType(ptr))
-                } else {
-                    Ok(default_obj)
-                }
+                let res = self.call(callable.as_ptr(), ptr);
+                unsafe { pyo3::ffi::Py_DECREF(callable.as_ptr()) };
+                res?.ok_or(Error::InvalidType(ptr))
             }
             None => Err(Error::InvalidType(ptr)),
         }
     }
 
+    /// Call the `__msgpack__` method of an object. The call counts against
+    /// the recursion limit of the default hook and must be followed by
+    /// `leave_call`.
+    pub fn enter_method_call(
+        &self,
+        method: *mut pyo3::ffi::PyObject,
+        ptr: *mut pyo3::ffi::PyObject,
+    ) -> Result<*mut pyo3::ffi::PyObject, Error> {
+        self.call(method, ptr)?.ok_or(Error::MethodFailed(ptr))
+    }
+
+    fn call(
+        &self,
+        callable: *mut pyo3::ffi::PyObject,
+        ptr: *mut pyo3::ffi::PyObject,
+    ) -> Result<Option<*mut pyo3::ffi::PyObject>, Error> {
+        let recursion = self.recursion.get();
+        if unlikely(recursion == RECURSION_LIMIT) {
+            return Err(Error::RecursionLimitReached);
+        }
+        self.recursion.set(recursion + 1);
+        let obj = unsafe { pyobject_call_one_arg(callable, ptr) };
+        if unlikely(obj.is_null()) {
+            Ok(None)
+        } else {
+            Ok(Some(obj))
+        }
+    }
+
     pub fn leave_call(&self) {
         let recursion = self.recursion.get();
         self.recursion.set(recursion - 1);
     }
+
+    /// Track the objects serialized with a nested serializer, whose
+    /// recursion is not counted by the outer serializer.
+    pub fn enter_nested(&self) -> bool {
+        let nested = self.nested.get();
+        if unlikely(nested == RECURSION_LIMIT) {
+            return false;
+        }
+        self.nested.set(nested + 1);
+        true
+    }
+
+    pub fn leave_nested(&self) {
+        let nested = self.nested.get();
+        self.nested.set(nested - 1);
+    }
 }
@@ -121,7 +121,7 @@ impl Dict<'_> {
                 )?;
             } else {
                 map.serialize_entry(
-                    &DictKey::new(key.as_ptr(), self.state, self.opts),
+                    &DictKey::new(key.as_ptr(), self.state, self.opts, self.default.ext_tags),
                     &PyObject::new(value.as_ptr(), self.state, self.opts, self.default),
                 )?;
             }
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::*;
+use crate::opt::*;
+use crate::state::State;
+use std::ptr::NonNull;
+
+/// Return a new `(value, name, qualified_name)` tuple of an enum member,
+/// where `qualified_name` is `"Class.NAME"`, or null on error. `name` and
+/// `qualified_name` are `None` if the member has no name.
+unsafe fn load_member(
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    let value = pyo3::ffi::PyObject_GetAttr(ptr, (*state).value_str);
+    if value.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        return std::ptr::null_mut();
+    }
+    let mut name = pyo3::ffi::PyObject_GetAttr(ptr, (*state).name_str);
+    if name.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        name = pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None());
+    } else if pyo3::ffi::PyUnicode_Check(name) == 0 {
+        pyo3::ffi::Py_DECREF(name);
+        name = pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None());
+    }
+    let qualified_name = if name == pyo3::ffi::Py_None() {
+        pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None())
+    } else {
+        // The tp_name of a class defined in Python is its __name__.
+        pyo3::ffi::PyUnicode_FromFormat(c"%s.%U".as_ptr(), (*ob_type!(ptr)).tp_name, name)
+    };
+    let member = pyo3::ffi::PyTuple_New(3);
+    pytuple_set_item(member, 0, value);
+    pytuple_set_item(member, 1, name);
+    pytuple_set_item(member, 2, qualified_name);
+    member
+}
+
+/// Return a new reference to the `(value, name, qualified_name)` tuple of an
+/// enum member. The tuples are cached per enum class in a dict keyed by the
+/// address of the members, so that their attributes are looked up once.
+fn enum_member(
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    let members = unsafe {
+        (*state)
+            .enum_members
+            .get(ob_type!(ptr), || NonNull::new(pyo3::ffi::PyDict_New()))?
+    };
+    unsafe {
+        let members = members.as_ptr();
+        let key = pyo3::ffi::PyLong_FromVoidPtr(ptr.cast());
+        let mut member = pyo3::ffi::PyDict_GetItem(members, key);
+        if member.is_null() {
+            member = load_member(ptr, state);
+            if !member.is_null() {
+                // Members are unique and kept alive by their class, except
+                // for pseudo-members of flags, which are kept alive by the
+                // dict so that their address is not reused.
+                let entry = pyo3::ffi::PyTuple_New(2);
+                pytuple_set_item(entry, 0, pyo3::ffi::Py_NewRef(ptr));
+                pytuple_set_item(entry, 1, member);
+                pyo3::ffi::PyDict_SetItem(members, key, entry);
+                pyo3::ffi::Py_DECREF(entry);
+                pyo3::ffi::Py_INCREF(member);
+            }
+        } else {
+            member = pyo3::ffi::Py_NewRef(pytuple_get_item(member, 1));
+        }
+        pyo3::ffi::Py_DECREF(key);
+        pyo3::ffi::Py_DECREF(members);
+        NonNull::new(member)
+    }
+}
+
+/// The form of an enum member to serialize, selected by the options.
+pub struct EnumMember {
+    member: NonNull<pyo3::ffi::PyObject>,
+    index: isize,
+}
+
+impl EnumMember {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+    ) -> Result<Self, &'static str> {
+        let member = enum_member(ptr, state).ok_or("Enum member has no value")?;
+        let mut index = if opts & ENUM_BY_QUALIFIED_NAME != 0 {
+            2
+        } else if opts & ENUM_BY_NAME != 0 {
+            1
+        } else {
+            0
+        };
+        // Members without a name, such as the empty member of a flag, are
+        // serialized as their value.
+        if unsafe { pytuple_get_item(member.as_ptr(), index) } == unsafe { pyo3::ffi::Py_None() } {
+            index = 0;
+        }
+        Ok(EnumMember {
+            member: member,
+            index: index,
+        })
+    }
+
+    /// Return a borrowed reference to the object to serialize, which is
+    /// owned until `self` is dropped.
+    pub fn as_ptr(&self) -> *mut pyo3::ffi::PyObject {
+        unsafe { pytuple_get_item(self.member.as_ptr(), self.index) }
+    }
+}
+
+impl Drop for EnumMember {
+    fn drop(&mut self) {
+        unsafe { pyo3::ffi::Py_DECREF(self.member.as_ptr()) };
+    }
+}
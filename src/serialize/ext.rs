@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::ext::PyExt;
-use crate::ffi::pybytes_as_bytes;
+use crate::ffi::*;
+use crate::msgpack;
 use crate::util::unlikely;
 use serde::ser::{Serialize, Serializer};
 use serde_bytes::Bytes;
+use std::ffi::CStr;
+use std::ptr::NonNull;
 
 #[repr(transparent)]
 pub struct Ext {
@@ -32,3 +35,113 @@ impl Serialize for Ext {
         serializer.serialize_newtype_variant("", tag as u32, "", Bytes::new(data))
     }
 }
+
+/// Return the extension type of an `ext_types` value, which may be any
+/// integer in the range `[-128, 127]`.
+fn registered_tag(tag: *mut pyo3::ffi::PyObject) -> Option<i8> {
+    if unsafe { pyo3::ffi::PyLong_Check(tag) } == 0 {
+        return None;
+    }
+    let tag = unsafe { pyo3::ffi::PyLong_AsLongLong(tag) };
+    if unlikely(tag == -1 && !unsafe { pyo3::ffi::PyErr_Occurred() }.is_null()) {
+        unsafe { pyo3::ffi::PyErr_Clear() };
+        return None;
+    }
+    if (-128..=127).contains(&tag) {
+        Some(tag as i8)
+    } else {
+        None
+    }
+}
+
+/// Check that `ext_types` maps classes to `(tag, encoder)` tuples, where
+/// `tag` is a valid extension type and `encoder` is callable.
+pub fn validate_ext_types(ext_types: *mut pyo3::ffi::PyObject) -> Result<(), &'static str> {
+    if unsafe { pyo3::ffi::PyDict_Check(ext_types) } == 0 {
+        return Err("Invalid ext_types");
+    }
+    for (key, value) in PyDictIter::from_pyobject(ext_types) {
+        if unsafe { pyo3::ffi::PyType_Check(key.as_ptr()) } == 0 {
+            return Err("ext_types keys must be classes");
+        }
+        let value = value.as_ptr();
+        if unsafe { pyo3::ffi::PyTuple_Check(value) } == 0
+            || unsafe { pyo3::ffi::Py_SIZE(value) } != 2
+        {
+            return Err("ext_types values must be (tag, encoder) tuples");
+        }
+        if registered_tag(unsafe { pytuple_get_item(value, 0) }).is_none() {
+            return Err("Extension type out of range");
+        }
+        if unsafe { pyo3::ffi::PyCallable_Check(pytuple_get_item(value, 1)) } == 0 {
+            return Err("ext_types encoder must be callable");
+        }
+    }
+    Ok(())
+}
+
+/// An object whose type is registered in the `ext_types` argument of
+/// `packb`, serialized as the extension type returned by its encoder.
+pub struct RegisteredExt {
+    ptr: *mut pyo3::ffi::PyObject,
+    entry: NonNull<pyo3::ffi::PyObject>,
+}
+
+impl RegisteredExt {
+    pub fn new(ptr: *mut pyo3::ffi::PyObject, entry: NonNull<pyo3::ffi::PyObject>) -> Self {
+        RegisteredExt {
+            ptr: ptr,
+            entry: entry,
+        }
+    }
+}
+
+impl Drop for RegisteredExt {
+    fn drop(&mut self) {
+        unsafe { pyo3::ffi::Py_DECREF(self.entry.as_ptr()) };
+    }
+}
+
+impl Serialize for RegisteredExt {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entry = self.entry.as_ptr();
+        if unlikely(
+            unsafe { pyo3::ffi::PyTuple_Check(entry) } == 0
+                || unsafe { pyo3::ffi::Py_SIZE(entry) } != 2,
+        ) {
+            return Err(serde::ser::Error::custom(
+                "ext_types values must be (tag, encoder) tuples",
+            ));
+        }
+        let tag = match registered_tag(unsafe { pytuple_get_item(entry, 0) }) {
+            Some(tag) => tag,
+            None => return Err(serde::ser::Error::custom("Extension type out of range")),
+        };
+        let encoder = unsafe { pytuple_get_item(entry, 1) };
+        let data = unsafe { pyobject_call_one_arg(encoder, self.ptr) };
+        if unlikely(data.is_null()) {
+            let name = unsafe { CStr::from_ptr((*ob_type!(self.ptr)).tp_name).to_string_lossy() };
+            return Err(serde::ser::Error::custom(format!(
+                "ext_types encoder failed: {name}"
+            )));
+        }
+        if unlikely(unsafe { pyo3::ffi::PyBytes_Check(data) } == 0) {
+            unsafe { pyo3::ffi::Py_DECREF(data) };
+            return Err(serde::ser::Error::custom(
+                "ext_types encoder must return bytes",
+            ));
+        }
+        let res = serializer.serialize_newtype_variant(
+            "",
+            msgpack::ext_variant_index(tag),
+            "",
+            Bytes::new(unsafe { pybytes_as_bytes(data) }),
+        );
+        unsafe { pyo3::ffi::Py_DECREF(data) };
+        res
+    }
+}
@@ -27,3 +27,15 @@ impl Serialize for Fragment {
         serializer.serialize_newtype_struct("", Bytes::new(data))
     }
 }
+
+/// A value already serialized by a nested serializer.
+pub struct EncodedItem<'a>(pub &'a [u8]);
+
+impl Serialize for EncodedItem<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("", Bytes::new(self.0))
+    }
+}
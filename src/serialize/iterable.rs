@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::exc::*;
+use crate::ffi::*;
+use crate::msgpack;
+use crate::opt::*;
+use crate::serialize::default::DefaultHook;
+use crate::serialize::fragment::EncodedItem;
+use crate::serialize::serializer::*;
+use crate::state::State;
+use crate::util::unlikely;
+
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+const ITERABLE_CHANGED_LENGTH: &str = "Iterable changed length during iteration";
+const ITERATION_FAILED: &str = "Iteration raised an exception";
+
+#[inline(always)]
+fn error_occurred() -> bool {
+    !unsafe { pyo3::ffi::PyErr_Occurred() }.is_null()
+}
+
+#[inline(always)]
+pub fn is_iterable(ob_type: *mut pyo3::ffi::PyTypeObject) -> bool {
+    unsafe {
+        (*ob_type).tp_iter.is_some()
+            && pyo3::ffi::PyType_HasFeature(
+                ob_type,
+                pyo3::ffi::Py_TPFLAGS_UNICODE_SUBCLASS
+                    | pyo3::ffi::Py_TPFLAGS_BYTES_SUBCLASS
+                    | pyo3::ffi::Py_TPFLAGS_DICT_SUBCLASS,
+            ) == 0
+    }
+}
+
+pub struct Iterable<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> Iterable<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        Iterable {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Serialize for Iterable<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = unsafe { pyo3::ffi::PyObject_Size(self.ptr) };
+        if len < 0 {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+        }
+        let iter = match unsafe { PyIter::from_pyobject(self.ptr) } {
+            Some(iter) => iter,
+            None => return Err(serde::ser::Error::custom(ITERATION_FAILED)),
+        };
+        if len < 0 {
+            self.serialize_buffered(serializer, iter)
+        } else {
+            self.serialize_sized(serializer, iter, len as usize)
+        }
+    }
+}
+
+impl Iterable<'_> {
+    fn serialize_sized<S>(&self, serializer: S, iter: PyIter, len: usize) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        let mut count = 0;
+        for item in iter {
+            if unlikely(count == len) {
+                return Err(serde::ser::Error::custom(ITERABLE_CHANGED_LENGTH));
+            }
+            let value = PyObject::new(item.as_ptr(), self.state, self.opts, self.default);
+            seq.serialize_element(&value)?;
+            count += 1;
+        }
+        if unlikely(error_occurred()) {
+            return Err(serde::ser::Error::custom(ITERATION_FAILED));
+        }
+        if unlikely(count != len) {
+            return Err(serde::ser::Error::custom(ITERABLE_CHANGED_LENGTH));
+        }
+        seq.end()
+    }
+
+    /// Serialize the elements of an iterable of unknown length, such as a
+    /// generator, to a buffer, since the length of an array precedes its
+    /// elements.
+    fn serialize_buffered<S>(&self, serializer: S, iter: PyIter) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if unlikely(!self.default.enter_nested()) {
+            return Err(serde::ser::Error::custom(RECURSION_LIMIT_REACHED));
+        }
+        let res = self.encode_items(iter);
+        self.default.leave_nested();
+        let (buffer, ends) = res.map_err(serde::ser::Error::custom)?;
+        let mut seq = serializer.serialize_seq(Some(ends.len()))?;
+        let mut start = 0;
+        for end in ends {
+            seq.serialize_element(&EncodedItem(&buffer[start..end]))?;
+            start = end;
+        }
+        seq.end()
+    }
+
+    fn encode_items(&self, iter: PyIter) -> Result<(Vec<u8>, Vec<usize>), String> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut ends: Vec<usize> = Vec::new();
+        for item in iter {
+            let value = PyObject::new(item.as_ptr(), self.state, self.opts, self.default);
+            value
+                .serialize(&mut msgpack::Serializer::new(&mut buffer))
+                .map_err(|err| err.to_string())?;
+            ends.push(buffer.len());
+        }
+        if unlikely(error_occurred()) {
+            return Err(ITERATION_FAILED.to_string());
+        }
+        Ok((buffer, ends))
+    }
+}
@@ -1,22 +1,38 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+mod attrs;
+mod bigint;
 mod bytearray;
 mod bytes;
+mod cache;
+mod collection;
 mod dataclass;
 mod datetime;
 mod datetimelike;
+mod decimal;
 mod default;
 mod dict;
+mod enumeration;
 mod ext;
 mod fragment;
+mod iterable;
 mod list;
 mod memoryview;
+mod namedtuple;
 mod numpy;
+mod protocol;
 mod pydantic;
 mod serializer;
+mod set;
 mod str;
+mod timestamp;
 mod tuple;
 mod uuid;
 mod writer;
 
+pub use attrs::is_attrs;
+pub use cache::TypeMap;
+pub use dataclass::{dataclass_fields, is_dataclass};
+pub use ext::validate_ext_types;
+pub use pydantic::is_pydantic_model;
 pub use serializer::serialize;
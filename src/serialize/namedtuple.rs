@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::*;
+use crate::opt::*;
+use crate::serialize::default::DefaultHook;
+use crate::serialize::serializer::*;
+use crate::state::State;
+use crate::util::unlikely;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::ptr::NonNull;
+
+/// Return the `_fields` of a namedtuple class, or `None` if the class is
+/// not a namedtuple.
+fn load_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let fields = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).namedtuple_fields_str);
+        if fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        if pyo3::ffi::PyTuple_CheckExact(fields) == 0 {
+            pyo3::ffi::Py_DECREF(fields);
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        for i in 0..pyo3::ffi::Py_SIZE(fields) {
+            if pyo3::ffi::PyUnicode_CheckExact(pytuple_get_item(fields, i)) == 0 {
+                pyo3::ffi::Py_DECREF(fields);
+                return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+            }
+        }
+        NonNull::new(fields)
+    }
+}
+
+/// Return a new reference to the field names of a namedtuple class, or
+/// `None` if the class is not a namedtuple. The result is cached per class.
+pub fn namedtuple_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    let fields = unsafe {
+        (*state)
+            .namedtuple_fields
+            .get(ob_type, || load_fields(ob_type, state))?
+    };
+    if fields.as_ptr() == unsafe { pyo3::ffi::Py_None() } {
+        unsafe { pyo3::ffi::Py_DECREF(fields.as_ptr()) };
+        None
+    } else {
+        Some(fields)
+    }
+}
+
+pub struct NamedTuple<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    fields: NonNull<pyo3::ffi::PyObject>,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> NamedTuple<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        fields: NonNull<pyo3::ffi::PyObject>,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        NamedTuple {
+            ptr: ptr,
+            fields: fields,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Drop for NamedTuple<'_> {
+    fn drop(&mut self) {
+        unsafe { pyo3::ffi::Py_DECREF(self.fields.as_ptr()) };
+    }
+}
+
+impl Serialize for NamedTuple<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields = self.fields.as_ptr();
+        let len = unsafe { pyo3::ffi::Py_SIZE(self.ptr) };
+        if unlikely(unsafe { pyo3::ffi::Py_SIZE(fields) } != len) {
+            return Err(serde::ser::Error::custom(
+                "namedtuple length does not match its fields",
+            ));
+        }
+        let mut map = serializer.serialize_map(Some(len as usize))?;
+        for i in 0..len {
+            let key = unsafe { pytuple_get_item(fields, i) };
+            let key_as_str = unicode_to_str(key).map_err(serde::ser::Error::custom)?;
+            let value = unsafe { pytuple_get_item(self.ptr, i) };
+            map.serialize_key(key_as_str).unwrap();
+            map.serialize_value(&PyObject::new(value, self.state, self.opts, self.default))?;
+        }
+        map.end()
+    }
+}
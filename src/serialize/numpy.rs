@@ -1,6 +1,7 @@
 use crate::ffi::*;
+use crate::msgpack;
 use crate::opt::*;
-use crate::serialize::datetimelike::NaiveDateTime;
+use crate::serialize::datetimelike::{serialize_duration, DurationLike, NaiveDateTime};
 use crate::state::State;
 use chrono::{DateTime, NaiveDate};
 use pyo3::ffi::*;
@@ -34,6 +35,9 @@ pub struct PyArrayInterface {
 #[derive(Clone, Copy)]
 enum ItemType {
     BOOL,
+    BYTES(usize),
+    C64,
+    C128,
     DATETIME64(NumpyDatetimeUnit),
     F16,
     F32,
@@ -42,6 +46,8 @@ enum ItemType {
     I16,
     I32,
     I64,
+    STR(usize),
+    TIMEDELTA64(NumpyDatetimeUnit, i8),
     U8,
     U16,
     U32,
@@ -53,13 +59,23 @@ impl ItemType {
         array: *mut PyArrayInterface,
         ptr: *mut PyObject,
         state: *mut State,
+        opts: Opt,
+        timedelta_tag: i8,
     ) -> Option<ItemType> {
         match unsafe { ((*array).typekind, (*array).itemsize) } {
             (098, 1) => Some(ItemType::BOOL),
+            (083, size) => Some(ItemType::BYTES(size as usize)),
+            (099, 8) => Some(ItemType::C64),
+            (099, 16) => Some(ItemType::C128),
             (077, 8) => {
                 let unit = NumpyDatetimeUnit::from_pyobject(ptr, state);
                 Some(ItemType::DATETIME64(unit))
             }
+            (085, size) => Some(ItemType::STR(size as usize)),
+            (109, 8) if opts & TIMEDELTA_OPTS != 0 => {
+                let unit = NumpyDatetimeUnit::from_pyobject(ptr, state);
+                Some(ItemType::TIMEDELTA64(unit, timedelta_tag))
+            }
             (102, 2) => Some(ItemType::F16),
             (102, 4) => Some(ItemType::F32),
             (102, 8) => Some(ItemType::F64),
@@ -76,19 +92,83 @@ impl ItemType {
     }
 }
 
+/// The options that enable the serialization of timedeltas.
+const TIMEDELTA_OPTS: Opt =
+    SERIALIZE_TIMEDELTA | TIMEDELTA_AS_EXT | TIMEDELTA_AS_MICROSECONDS | TIMEDELTA_AS_SECONDS;
+
 pub enum PyArrayError {
+    LongDouble,
     Malformed,
-    NotContiguous,
     UnsupportedDataType,
+    ZeroDimensional,
 }
 
 struct NumpyArrayData {
     data: *const c_void,
     len: usize,
+    stride: isize,
     kind: ItemType,
     opts: Opt,
 }
 
+impl NumpyArrayData {
+    /// Return an iterator over the items, which are `stride` bytes apart.
+    fn items<T: Copy>(&self) -> impl Iterator<Item = T> + '_ {
+        let data = self.data.cast::<u8>();
+        (0..self.len).map(move |i| unsafe {
+            data.offset(i as isize * self.stride)
+                .cast::<T>()
+                .read_unaligned()
+        })
+    }
+
+    /// Return an iterator over the items of `size` bytes as slices.
+    fn slices(&self, size: usize) -> impl Iterator<Item = &[u8]> + '_ {
+        let data = self.data.cast::<u8>();
+        (0..self.len).map(move |i| unsafe {
+            std::slice::from_raw_parts(data.offset(i as isize * self.stride), size)
+        })
+    }
+}
+
+/// Strip the trailing null bytes of a fixed-size numpy string.
+fn strip_nulls(value: &[u8]) -> &[u8] {
+    let len = value.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    &value[..len]
+}
+
+/// Decode a fixed-size numpy unicode string, which is UCS-4 encoded in
+/// native byte order and padded with null characters.
+fn decode_ucs4(value: &[u8], buf: &mut String) -> Result<(), &'static str> {
+    buf.clear();
+    let mut len = 0;
+    for (i, chunk) in value.chunks_exact(4).enumerate() {
+        if chunk != [0, 0, 0, 0] {
+            len = i + 1;
+        }
+    }
+    for chunk in value.chunks_exact(4).take(len) {
+        let code = u32::from_ne_bytes(chunk.try_into().unwrap());
+        buf.push(char::from_u32(code).ok_or("numpy array contains an invalid str")?);
+    }
+    Ok(())
+}
+
+/// A complex number, serialized as an array of its real and imaginary parts.
+struct Complex<T>([T; 2]);
+
+impl<T: Serialize> Serialize for Complex<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        seq.serialize_element(&self.0[0])?;
+        seq.serialize_element(&self.0[1])?;
+        seq.end()
+    }
+}
+
 impl Serialize for NumpyArrayData {
     #[inline(never)]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -98,17 +178,29 @@ impl Serialize for NumpyArrayData {
         let mut seq = serializer.serialize_seq(Some(self.len))?;
         match self.kind {
             ItemType::BOOL => {
-                let slice: &[u8] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u8>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u8>() {
                     let value = each == 1;
                     seq.serialize_element(&value).unwrap();
                 }
             }
+            ItemType::BYTES(size) => {
+                for each in self.slices(size) {
+                    seq.serialize_element(serde_bytes::Bytes::new(strip_nulls(each)))
+                        .unwrap();
+                }
+            }
+            ItemType::C64 => {
+                for each in self.items::<[f32; 2]>() {
+                    seq.serialize_element(&Complex(each)).unwrap();
+                }
+            }
+            ItemType::C128 => {
+                for each in self.items::<[f64; 2]>() {
+                    seq.serialize_element(&Complex(each)).unwrap();
+                }
+            }
             ItemType::DATETIME64(unit) => {
-                let slice: &[i64] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<i64>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<i64>() {
                     let value = unit
                         .datetime(each, self.opts)
                         .map_err(serde::ser::Error::custom)?;
@@ -116,80 +208,73 @@ impl Serialize for NumpyArrayData {
                 }
             }
             ItemType::F16 => {
-                let slice: &[u16] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u16>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u16>() {
                     let value = half::f16::from_bits(each).to_f32();
                     seq.serialize_element(&value).unwrap();
                 }
             }
             ItemType::F32 => {
-                let slice: &[f32] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<f32>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<f32>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::F64 => {
-                let slice: &[f64] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<f64>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<f64>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::I8 => {
-                let slice: &[i8] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<i8>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<i8>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::I16 => {
-                let slice: &[i16] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<i16>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<i16>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::I32 => {
-                let slice: &[i32] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<i32>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<i32>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::I64 => {
-                let slice: &[i64] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<i64>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<i64>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
+            ItemType::STR(size) => {
+                let mut buf = String::new();
+                for each in self.slices(size) {
+                    decode_ucs4(each, &mut buf).map_err(serde::ser::Error::custom)?;
+                    seq.serialize_element(buf.as_str()).unwrap();
+                }
+            }
+            ItemType::TIMEDELTA64(unit, tag) => {
+                for each in self.items::<i64>() {
+                    let value = unit
+                        .timedelta(each, self.opts, tag)
+                        .map_err(serde::ser::Error::custom)?;
+                    seq.serialize_element(&value)?;
+                }
+            }
             ItemType::U8 => {
-                let slice: &[u8] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u8>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u8>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::U16 => {
-                let slice: &[u16] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u16>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u16>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::U32 => {
-                let slice: &[u32] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u32>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u32>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
             ItemType::U64 => {
-                let slice: &[u64] =
-                    unsafe { std::slice::from_raw_parts(self.data.cast::<u64>(), self.len) };
-                for &each in slice.iter() {
+                for each in self.items::<u64>() {
                     seq.serialize_element(&each).unwrap();
                 }
             }
@@ -235,7 +320,12 @@ pub struct NumpyArray {
 
 impl NumpyArray {
     #[inline(never)]
-    pub fn new(ptr: *mut PyObject, state: *mut State, opts: Opt) -> Result<Self, PyArrayError> {
+    pub fn new(
+        ptr: *mut PyObject,
+        state: *mut State,
+        opts: Opt,
+        timedelta_tag: i8,
+    ) -> Result<Self, PyArrayError> {
         unsafe {
             let capsule = pyo3::ffi::PyObject_GetAttr(ptr, (*state).array_struct_str);
             let array = (*capsule.cast::<PyCapsule>())
@@ -245,32 +335,32 @@ impl NumpyArray {
                 pyo3::ffi::Py_DECREF(capsule);
                 return Err(PyArrayError::Malformed);
             }
-            if (*array).flags & 0x1 != 0x1 {
-                pyo3::ffi::Py_DECREF(capsule);
-                return Err(PyArrayError::NotContiguous);
-            }
             let num_dimensions = (*array).nd as usize;
             if num_dimensions == 0 {
                 pyo3::ffi::Py_DECREF(capsule);
-                return Err(PyArrayError::UnsupportedDataType);
+                return Err(PyArrayError::ZeroDimensional);
             }
-            match ItemType::find(array, ptr, state) {
+            if (*array).typekind == 102 && (*array).itemsize > 8 {
+                pyo3::ffi::Py_DECREF(capsule);
+                return Err(PyArrayError::LongDouble);
+            }
+            match ItemType::find(array, ptr, state, opts, timedelta_tag) {
                 None => {
                     pyo3::ffi::Py_DECREF(capsule);
                     Err(PyArrayError::UnsupportedDataType)
                 }
                 Some(kind) => {
+                    let shape =
+                        std::slice::from_raw_parts((*array).shape.cast::<isize>(), num_dimensions);
+                    let strides = NumpyArray::strides(array);
                     let root = if num_dimensions > 1 {
                         let mut position = Vec::with_capacity(num_dimensions);
-                        NumpyArray::build(array, kind, opts, 0, &mut position)
+                        NumpyArray::build(array, shape, &strides, kind, opts, 0, &mut position)
                     } else {
-                        let shape = std::slice::from_raw_parts(
-                            (*array).shape.cast::<isize>(),
-                            num_dimensions,
-                        );
                         NumpyArrayNode::Leaf(NumpyArrayData {
                             data: (*array).data,
                             len: shape[0] as usize,
+                            stride: strides[0],
                             kind: kind,
                             opts: opts,
                         })
@@ -284,24 +374,41 @@ impl NumpyArray {
         }
     }
 
+    /// Return the strides of an array, which are those of a C-contiguous
+    /// array if the interface does not provide them.
+    fn strides(array: *mut PyArrayInterface) -> Vec<isize> {
+        let num_dimensions = unsafe { (*array).nd as usize };
+        if unsafe { (*array).strides }.is_null() {
+            let shape = unsafe {
+                std::slice::from_raw_parts((*array).shape.cast::<isize>(), num_dimensions)
+            };
+            let mut strides = vec![unsafe { (*array).itemsize } as isize; num_dimensions];
+            for i in (0..num_dimensions - 1).rev() {
+                strides[i] = strides[i + 1] * shape[i + 1];
+            }
+            strides
+        } else {
+            unsafe { std::slice::from_raw_parts((*array).strides.cast::<isize>(), num_dimensions) }
+                .to_vec()
+        }
+    }
+
     fn build(
         array: *mut PyArrayInterface,
+        shape: &[isize],
+        strides: &[isize],
         kind: ItemType,
         opts: Opt,
         depth: usize,
         position: &mut Vec<isize>,
     ) -> NumpyArrayNode {
-        let num_dimensions = unsafe { (*array).nd as usize };
-        let shape =
-            unsafe { std::slice::from_raw_parts((*array).shape.cast::<isize>(), num_dimensions) };
-        let strides =
-            unsafe { std::slice::from_raw_parts((*array).strides.cast::<isize>(), num_dimensions) };
+        let num_dimensions = shape.len();
         let num_children = shape[depth];
         let mut children = Vec::with_capacity(num_children as usize);
         for i in 0..num_children {
             position.push(i);
             let child = if depth < num_dimensions - 2 {
-                NumpyArray::build(array, kind, opts, depth + 1, position)
+                NumpyArray::build(array, shape, strides, kind, opts, depth + 1, position)
             } else {
                 let offset = strides
                     .iter()
@@ -311,6 +418,7 @@ impl NumpyArray {
                 NumpyArrayNode::Leaf(NumpyArrayData {
                     data: unsafe { (*array).data.offset(offset) },
                     len: shape[num_dimensions - 1] as usize,
+                    stride: strides[num_dimensions - 1],
                     kind: kind,
                     opts: opts,
                 })
@@ -337,6 +445,177 @@ impl Serialize for NumpyArray {
     }
 }
 
+/// A numpy array serialized by `OPT_NUMPY_AS_EXT` as an extension type whose
+/// value is the length of the dtype descriptor as an 8-bit integer, the
+/// little-endian descriptor, the number of dimensions as an 8-bit integer,
+/// each dimension as a 64-bit big-endian integer and the items in C order
+/// and little-endian byte order.
+pub struct NumpyArrayExt {
+    capsule: *mut PyObject,
+    descr: *mut PyObject,
+    tag: i8,
+}
+
+impl NumpyArrayExt {
+    /// Return `None` if the datatype of the array is not supported, in which
+    /// case the array is serialized as an array of its items.
+    #[inline(never)]
+    pub fn new(ptr: *mut PyObject, state: *mut State, tag: i8) -> Option<Self> {
+        unsafe {
+            let capsule = pyo3::ffi::PyObject_GetAttr(ptr, (*state).array_struct_str);
+            if capsule.is_null() {
+                pyo3::ffi::PyErr_Clear();
+                return None;
+            }
+            let array = (*capsule.cast::<PyCapsule>())
+                .pointer
+                .cast::<PyArrayInterface>();
+            // longdouble and clongdouble have a platform-dependent format
+            let supported = (*array).two == 2
+                && match ((*array).typekind, (*array).itemsize) {
+                    (098, 1) => true,
+                    (102, size) => size <= 8,
+                    (099, size) => size <= 16,
+                    (077 | 083 | 085 | 105 | 109 | 117, _) => true,
+                    _ => false,
+                };
+            if !supported {
+                pyo3::ffi::Py_DECREF(capsule);
+                return None;
+            }
+            let dtype = pyo3::ffi::PyObject_GetAttr(ptr, (*state).dtype_str);
+            let descr = if dtype.is_null() {
+                std::ptr::null_mut()
+            } else {
+                let descr = pyo3::ffi::PyObject_GetAttr(dtype, (*state).str_str);
+                pyo3::ffi::Py_DECREF(dtype);
+                descr
+            };
+            if descr.is_null() || pyo3::ffi::PyUnicode_Check(descr) == 0 {
+                pyo3::ffi::PyErr_Clear();
+                pyo3::ffi::Py_XDECREF(descr);
+                pyo3::ffi::Py_DECREF(capsule);
+                return None;
+            }
+            Some(NumpyArrayExt {
+                capsule: capsule,
+                descr: descr,
+                tag: tag,
+            })
+        }
+    }
+
+    fn write_ext(&self, buffer: &mut Vec<u8>) -> Result<(), &'static str> {
+        let array = unsafe {
+            (*self.capsule.cast::<PyCapsule>())
+                .pointer
+                .cast::<PyArrayInterface>()
+        };
+        let descr = unicode_to_str(self.descr)
+            .map_err(|_| "numpy array has an invalid dtype")?
+            .as_bytes();
+        if descr.is_empty() || descr.len() > 255 {
+            return Err("numpy array has an invalid dtype");
+        }
+        // the descriptor starts with '>' if the items are big-endian
+        let swap = descr[0] == b'>';
+        buffer.push(descr.len() as u8);
+        buffer.push(if swap { b'<' } else { descr[0] });
+        buffer.extend_from_slice(&descr[1..]);
+
+        let num_dimensions = unsafe { (*array).nd as usize };
+        if num_dimensions > 255 {
+            return Err("numpy array has too many dimensions");
+        }
+        let (shape, strides) = if num_dimensions == 0 {
+            (&[][..], Vec::new())
+        } else {
+            let shape = unsafe {
+                std::slice::from_raw_parts((*array).shape.cast::<isize>(), num_dimensions)
+            };
+            (shape, NumpyArray::strides(array))
+        };
+        buffer.push(num_dimensions as u8);
+        for &dim in shape {
+            buffer.extend_from_slice(&(dim as u64).to_be_bytes());
+        }
+
+        let itemsize = unsafe { (*array).itemsize } as usize;
+        let count = shape.iter().product::<isize>() as usize;
+        let data = unsafe { (*array).data.cast::<u8>() };
+        if count == 0 {
+            return Ok(());
+        }
+        let mut expected = itemsize as isize;
+        let mut contiguous = true;
+        for (&dim, &stride) in shape.iter().zip(strides.iter()).rev() {
+            contiguous &= dim == 1 || stride == expected;
+            expected *= dim;
+        }
+        if contiguous && !swap {
+            buffer.extend_from_slice(unsafe { std::slice::from_raw_parts(data, count * itemsize) });
+            return Ok(());
+        }
+        // the unit of the byte order, which is that of the parts of complex
+        // numbers and of the characters of unicode strings
+        let unit = match unsafe { (*array).typekind } {
+            099 => itemsize / 2,
+            085 => 4,
+            _ => itemsize,
+        };
+        buffer.reserve(count * itemsize);
+        let mut index = vec![0isize; num_dimensions];
+        let mut offset = 0isize;
+        for _ in 0..count {
+            let item = unsafe { std::slice::from_raw_parts(data.offset(offset), itemsize) };
+            if swap {
+                for each in item.chunks_exact(unit) {
+                    buffer.extend(each.iter().rev());
+                }
+            } else {
+                buffer.extend_from_slice(item);
+            }
+            for i in (0..num_dimensions).rev() {
+                index[i] += 1;
+                offset += strides[i];
+                if index[i] < shape[i] {
+                    break;
+                }
+                offset -= strides[i] * shape[i];
+                index[i] = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for NumpyArrayExt {
+    fn drop(&mut self) {
+        unsafe {
+            pyo3::ffi::Py_DECREF(self.descr);
+            pyo3::ffi::Py_DECREF(self.capsule);
+        }
+    }
+}
+
+impl Serialize for NumpyArrayExt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buffer = Vec::new();
+        match self.write_ext(&mut buffer) {
+            Ok(()) => serializer.serialize_newtype_variant(
+                "",
+                msgpack::ext_variant_index(self.tag),
+                "",
+                serde_bytes::Bytes::new(&buffer),
+            ),
+            Err(err) => Err(serde::ser::Error::custom(err)),
+        }
+    }
+}
+
 /// This mimicks the units supported by numpy's datetime64 type.
 ///
 /// See
@@ -386,6 +665,9 @@ impl std::fmt::Display for NumpyDatetimeUnit {
 enum NumpyDateTimeError {
     UnsupportedUnit(NumpyDatetimeUnit),
     Unrepresentable { unit: NumpyDatetimeUnit, val: i64 },
+    UnsupportedTimedeltaUnit(NumpyDatetimeUnit),
+    UnrepresentableTimedelta { unit: NumpyDatetimeUnit, val: i64 },
+    TimedeltaNaT,
 }
 
 impl std::fmt::Display for NumpyDateTimeError {
@@ -395,6 +677,13 @@ impl std::fmt::Display for NumpyDateTimeError {
             Self::Unrepresentable { unit, val } => {
                 write!(f, "unrepresentable numpy.datetime64: {val} {unit}")
             }
+            Self::UnsupportedTimedeltaUnit(unit) => {
+                write!(f, "unsupported numpy.timedelta64 unit: {unit}")
+            }
+            Self::UnrepresentableTimedelta { unit, val } => {
+                write!(f, "unrepresentable numpy.timedelta64: {val} {unit}")
+            }
+            Self::TimedeltaNaT => write!(f, "unrepresentable numpy.timedelta64: NaT"),
         }
     }
 }
@@ -510,6 +799,83 @@ impl NumpyDatetimeUnit {
         }
         .map(|dt| NaiveDateTime { dt, opts })
     }
+
+    /// Return a `NumpyTimedelta` for a value in array with this unit,
+    /// truncated to microseconds.
+    ///
+    /// Returns an `Err(NumpyDateTimeError)` if the value is invalid for this
+    /// unit or out of the range of `datetime.timedelta`.
+    fn timedelta(
+        &self,
+        val: i64,
+        opts: Opt,
+        tag: i8,
+    ) -> Result<NumpyTimedelta, NumpyDateTimeError> {
+        const DAY: i128 = 86_400_000_000;
+        // the value in microseconds is val * mul / div
+        let (mul, div): (i128, i128) = match self {
+            Self::Weeks => (7 * DAY, 1),
+            Self::Days => (DAY, 1),
+            Self::Hours => (3_600_000_000, 1),
+            Self::Minutes => (60_000_000, 1),
+            Self::Seconds => (1_000_000, 1),
+            Self::Milliseconds => (1_000, 1),
+            Self::Microseconds => (1, 1),
+            Self::Nanoseconds => (1, 1_000),
+            Self::Picoseconds => (1, 1_000_000),
+            Self::Femtoseconds => (1, 1_000_000_000),
+            Self::Attoseconds => (1, 1_000_000_000_000),
+            _ => return Err(NumpyDateTimeError::UnsupportedTimedeltaUnit(*self)),
+        };
+        if val == i64::MIN {
+            return Err(NumpyDateTimeError::TimedeltaNaT);
+        }
+        let value = (i128::from(val) * mul).div_euclid(div);
+        let days = value.div_euclid(DAY);
+        // the range of datetime.timedelta
+        if days.abs() > 999_999_999 {
+            return Err(NumpyDateTimeError::UnrepresentableTimedelta { unit: *self, val });
+        }
+        let value = value.rem_euclid(DAY);
+        Ok(NumpyTimedelta {
+            days: days as i32,
+            seconds: (value / 1_000_000) as i32,
+            microseconds: (value % 1_000_000) as i32,
+            opts: opts,
+            tag: tag,
+        })
+    }
+}
+
+struct NumpyTimedelta {
+    days: i32,
+    seconds: i32,
+    microseconds: i32,
+    opts: Opt,
+    tag: i8,
+}
+
+impl DurationLike for NumpyTimedelta {
+    fn days(&self) -> i32 {
+        self.days
+    }
+
+    fn seconds(&self) -> i32 {
+        self.seconds
+    }
+
+    fn microseconds(&self) -> i32 {
+        self.microseconds
+    }
+}
+
+impl Serialize for NumpyTimedelta {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_duration(self, self.opts, self.tag, serializer)
+    }
 }
 
 macro_rules! define_numpy_type {
@@ -554,6 +920,10 @@ define_numpy_type!(NumpyUint8, NumpyUint8Object, u8);
 define_numpy_type!(NumpyUint16, NumpyUint16Object, u16);
 define_numpy_type!(NumpyUint32, NumpyUint32Object, u32);
 define_numpy_type!(NumpyUint64, NumpyUint64Object, u64);
+define_numpy_type!(NumpyIntc, NumpyIntcObject, i32);
+define_numpy_type!(NumpyUintc, NumpyUintcObject, u32);
+define_numpy_type!(NumpyLonglong, NumpyLonglongObject, i64);
+define_numpy_type!(NumpyUlonglong, NumpyUlonglongObject, u64);
 
 #[repr(C)]
 struct NumpyDatetime64Object {
@@ -612,3 +982,103 @@ impl Serialize for NumpyFloat16 {
         half::f16::from_bits(value).to_f32().serialize(serializer)
     }
 }
+
+#[repr(C)]
+struct NumpyTimedelta64Object {
+    ob_base: PyObject,
+    value: i64,
+}
+
+pub struct NumpyTimedelta64 {
+    ptr: *mut PyObject,
+    state: *mut State,
+    opts: Opt,
+    tag: i8,
+}
+
+impl NumpyTimedelta64 {
+    pub fn new(ptr: *mut PyObject, state: *mut State, opts: Opt, tag: i8) -> Self {
+        NumpyTimedelta64 {
+            ptr,
+            state,
+            opts,
+            tag,
+        }
+    }
+}
+
+impl Serialize for NumpyTimedelta64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let unit = NumpyDatetimeUnit::from_pyobject(self.ptr, self.state);
+        let value = unsafe { (*self.ptr.cast::<NumpyTimedelta64Object>()).value };
+        unit.timedelta(value, self.opts, self.tag)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+macro_rules! define_numpy_complex_type {
+    ($name:ident, $object_name:ident, $type:ty) => {
+        #[repr(C)]
+        struct $object_name {
+            ob_base: PyObject,
+            value: [$type; 2],
+        }
+
+        #[repr(transparent)]
+        pub struct $name {
+            ptr: *mut PyObject,
+        }
+
+        impl $name {
+            pub fn new(ptr: *mut PyObject) -> Self {
+                $name { ptr }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let value = unsafe { (*self.ptr.cast::<$object_name>()).value };
+                Complex(value).serialize(serializer)
+            }
+        }
+    };
+}
+
+define_numpy_complex_type!(NumpyComplex64, NumpyComplex64Object, f32);
+define_numpy_complex_type!(NumpyComplex128, NumpyComplex128Object, f64);
+
+/// A `numpy.longdouble`, serialized as a float64 since its format depends on
+/// the platform.
+#[repr(transparent)]
+pub struct NumpyLongDouble {
+    ptr: *mut PyObject,
+}
+
+impl NumpyLongDouble {
+    pub fn new(ptr: *mut PyObject) -> Self {
+        NumpyLongDouble { ptr }
+    }
+}
+
+impl Serialize for NumpyLongDouble {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = unsafe { pyo3::ffi::PyFloat_AsDouble(self.ptr) };
+        if value == -1.0 && !unsafe { pyo3::ffi::PyErr_Occurred() }.is_null() {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(serde::ser::Error::custom(
+                "numpy.longdouble could not be converted to float",
+            ));
+        }
+        value.serialize(serializer)
+    }
+}
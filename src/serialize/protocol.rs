@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::state::State;
+use std::ptr::NonNull;
+
+fn load_msgpack_method(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let method = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).msgpack_str);
+        if method.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return NonNull::new(pyo3::ffi::Py_NewRef(pyo3::ffi::Py_None()));
+        }
+        NonNull::new(method)
+    }
+}
+
+/// Return a new reference to the `__msgpack__` method of a type, or `None`
+/// if the type does not define it. The result is cached per type.
+pub fn msgpack_method(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    let method = unsafe {
+        (*state)
+            .msgpack_methods
+            .get(ob_type, || load_msgpack_method(ob_type, state))?
+    };
+    if method.as_ptr() == unsafe { pyo3::ffi::Py_None() } {
+        unsafe { pyo3::ffi::Py_DECREF(method.as_ptr()) };
+        None
+    } else {
+        Some(method)
+    }
+}
@@ -8,9 +8,12 @@ use crate::serialize::serializer::*;
 use crate::state::State;
 use crate::util::unlikely;
 
-use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
 use smallvec::SmallVec;
+use std::ptr::NonNull;
+
+const PYDANTIC_FIELDS_OPTS: Opt = PYDANTIC_BY_ALIAS | PYDANTIC_COMPUTED_FIELDS | PYDANTIC_EXCLUDE;
 
 #[inline]
 pub fn is_pydantic_model(ob_type: *mut pyo3::ffi::PyTypeObject, state: *mut State) -> bool {
@@ -22,6 +25,160 @@ pub fn is_pydantic_model(ob_type: *mut pyo3::ffi::PyTypeObject, state: *mut Stat
     }
 }
 
+/// Return the alias of a field or computed field, preferring the
+/// serialization alias, or null if the field has no alias.
+unsafe fn load_alias(
+    info: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    for attr in [(*state).serialization_alias_str, (*state).alias_str] {
+        let alias = pyo3::ffi::PyObject_GetAttr(info, attr);
+        if alias.is_null() {
+            pyo3::ffi::PyErr_Clear();
+        } else if pyo3::ffi::PyUnicode_Check(alias) != 0 {
+            return alias;
+        } else {
+            pyo3::ffi::Py_DECREF(alias);
+        }
+    }
+    std::ptr::null_mut()
+}
+
+/// Return the computed fields of a model class as a tuple of
+/// `(name, alias)` tuples, where `alias` is the name if there is no alias.
+unsafe fn load_computed_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> *mut pyo3::ffi::PyObject {
+    let computed = pyo3::ffi::PyList_New(0);
+    let decorators = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).pydantic_decorators_str);
+    if decorators.is_null() {
+        pyo3::ffi::PyErr_Clear();
+    } else {
+        let fields = pyo3::ffi::PyObject_GetAttr(decorators, (*state).computed_fields_str);
+        if fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+        } else {
+            if pyo3::ffi::PyDict_Check(fields) != 0 {
+                for (name, decorator) in PyDictIter::from_pyobject(fields) {
+                    let info = pyo3::ffi::PyObject_GetAttr(decorator.as_ptr(), (*state).info_str);
+                    let mut alias = std::ptr::null_mut();
+                    if info.is_null() {
+                        pyo3::ffi::PyErr_Clear();
+                    } else {
+                        alias = load_alias(info, state);
+                        pyo3::ffi::Py_DECREF(info);
+                    }
+                    if alias.is_null() {
+                        alias = pyo3::ffi::Py_NewRef(name.as_ptr());
+                    }
+                    let item = pyo3::ffi::PyTuple_New(2);
+                    pytuple_set_item(item, 0, pyo3::ffi::Py_NewRef(name.as_ptr()));
+                    pytuple_set_item(item, 1, alias);
+                    pyo3::ffi::PyList_Append(computed, item);
+                    pyo3::ffi::Py_DECREF(item);
+                }
+            }
+            pyo3::ffi::Py_DECREF(fields);
+        }
+        pyo3::ffi::Py_DECREF(decorators);
+    }
+    let tuple = pyo3::ffi::PyList_AsTuple(computed);
+    pyo3::ffi::Py_DECREF(computed);
+    tuple
+}
+
+/// Return whether a field is excluded from serialization, reading
+/// `FieldInfo.exclude` in pydantic v2 and `ModelField.field_info.exclude` in
+/// pydantic v1.
+unsafe fn is_excluded(info: *mut pyo3::ffi::PyObject, state: *mut State) -> bool {
+    let mut exclude = pyo3::ffi::PyObject_GetAttr(info, (*state).exclude_str);
+    if exclude.is_null() {
+        pyo3::ffi::PyErr_Clear();
+        let field_info = pyo3::ffi::PyObject_GetAttrString(info, c"field_info".as_ptr());
+        if field_info.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return false;
+        }
+        exclude = pyo3::ffi::PyObject_GetAttr(field_info, (*state).exclude_str);
+        pyo3::ffi::Py_DECREF(field_info);
+        if exclude.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            return false;
+        }
+    }
+    pyo3::ffi::Py_DECREF(exclude);
+    exclude == pyo3::ffi::Py_True()
+}
+
+/// Return the field metadata of a model class as an `(aliases, excluded,
+/// computed)` tuple, where `aliases` maps field names to their aliases,
+/// `excluded` is the set of the names of the excluded fields and `computed`
+/// contains the computed fields.
+fn load_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        let aliases = pyo3::ffi::PyDict_New();
+        let excluded = pyo3::ffi::PySet_New(std::ptr::null_mut());
+        // model_fields in pydantic v2, __fields__ in pydantic v1
+        let mut model_fields =
+            pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).model_fields_str);
+        if model_fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+            model_fields = pyo3::ffi::PyObject_GetAttr(ob_type.cast(), (*state).fields_str);
+        }
+        if model_fields.is_null() {
+            pyo3::ffi::PyErr_Clear();
+        } else {
+            if pyo3::ffi::PyDict_Check(model_fields) != 0 {
+                for (name, info) in PyDictIter::from_pyobject(model_fields) {
+                    let alias = load_alias(info.as_ptr(), state);
+                    if !alias.is_null() {
+                        pyo3::ffi::PyDict_SetItem(aliases, name.as_ptr(), alias);
+                        pyo3::ffi::Py_DECREF(alias);
+                    }
+                    if is_excluded(info.as_ptr(), state) {
+                        pyo3::ffi::PySet_Add(excluded, name.as_ptr());
+                    }
+                }
+            }
+            pyo3::ffi::Py_DECREF(model_fields);
+        }
+        let fields = pyo3::ffi::PyTuple_New(3);
+        pytuple_set_item(fields, 0, aliases);
+        pytuple_set_item(fields, 1, excluded);
+        pytuple_set_item(fields, 2, load_computed_fields(ob_type, state));
+        NonNull::new(fields)
+    }
+}
+
+/// Return a new reference to the field metadata of a model class. The
+/// result is cached per class.
+fn pydantic_fields(
+    ob_type: *mut pyo3::ffi::PyTypeObject,
+    state: *mut State,
+) -> Option<NonNull<pyo3::ffi::PyObject>> {
+    unsafe {
+        (*state)
+            .pydantic_fields
+            .get(ob_type, || load_fields(ob_type, state))
+    }
+}
+
+/// The values of the computed fields of a model, which are owned until the
+/// model is serialized.
+struct ComputedValues(SmallVec<[*mut pyo3::ffi::PyObject; 4]>);
+
+impl Drop for ComputedValues {
+    fn drop(&mut self) {
+        for value in self.0.iter() {
+            unsafe { pyo3::ffi::Py_DECREF(*value) };
+        }
+    }
+}
+
 pub struct PydanticModel<'a> {
     ptr: *mut pyo3::ffi::PyObject,
     state: *mut State,
@@ -58,30 +215,46 @@ impl Serialize for PydanticModel<'_> {
             ));
         }
 
+        if unlikely(self.opts & STRUCT_AS_ARRAY != 0) {
+            let res = self.serialize_as_array(serializer, dict);
+            unsafe { pyo3::ffi::Py_DECREF(dict) };
+            return res;
+        }
+
+        let fields = if self.opts & PYDANTIC_FIELDS_OPTS != 0 {
+            pydantic_fields(ob_type!(self.ptr), self.state)
+        } else {
+            None
+        };
+
         let extra_dict =
             unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).pydantic_extra_str) };
-        if extra_dict.is_null() {
+        let res = if extra_dict.is_null() {
             unsafe { pyo3::ffi::PyErr_Clear() };
-            let res = self.serialize_with_no_extra(serializer, dict);
+            let res = self.serialize_with_no_extra(serializer, dict, fields);
             unsafe { pyo3::ffi::Py_DECREF(dict) };
             res
         } else {
             let res = if ob_type!(extra_dict) == &raw mut pyo3::ffi::PyDict_Type {
-                self.serialize_with_extra(serializer, dict, extra_dict)
+                self.serialize_with_extra(serializer, dict, extra_dict, fields)
             } else {
-                self.serialize_with_no_extra(serializer, dict)
+                self.serialize_with_no_extra(serializer, dict, fields)
             };
             unsafe {
                 pyo3::ffi::Py_DECREF(dict);
                 pyo3::ffi::Py_DECREF(extra_dict)
             };
             res
+        };
+        if let Some(fields) = fields {
+            unsafe { pyo3::ffi::Py_DECREF(fields.as_ptr()) };
         }
+        res
     }
 }
 
 impl PydanticModel<'_> {
-    fn serialize_with_no_extra<S>(
+    fn serialize_as_array<S>(
         &self,
         serializer: S,
         dict: *mut pyo3::ffi::PyObject,
@@ -90,11 +263,7 @@ impl PydanticModel<'_> {
         S: Serializer,
     {
         let len = unsafe { pydict_size(dict) } as usize;
-        if unlikely(len == 0) {
-            return serializer.serialize_map(Some(0))?.end();
-        }
-        let mut items: SmallVec<[(&str, *mut pyo3::ffi::PyObject); 8]> =
-            SmallVec::with_capacity(len);
+        let mut values: SmallVec<[*mut pyo3::ffi::PyObject; 8]> = SmallVec::with_capacity(len);
         for (key, value) in PyDictIter::from_pyobject(dict) {
             if unlikely(ob_type!(key.as_ptr()) != &raw mut pyo3::ffi::PyUnicode_Type) {
                 return Err(serde::ser::Error::custom(KEY_MUST_BE_STR));
@@ -103,20 +272,25 @@ impl PydanticModel<'_> {
             if unlikely(key_as_str.as_bytes()[0] == b'_') {
                 continue;
             }
-            items.push((key_as_str, value.as_ptr()));
+            values.push(value.as_ptr());
         }
-
-        if self.opts & SORT_KEYS != 0 {
-            items.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values.iter() {
+            seq.serialize_element(&PyObject::new(*value, self.state, self.opts, self.default))?;
         }
+        seq.end()
+    }
 
-        let mut map = serializer.serialize_map(Some(items.len()))?;
-        for (key, value) in items.iter() {
-            let pyvalue = PyObject::new(*value, self.state, self.opts, self.default);
-            map.serialize_key(key).unwrap();
-            map.serialize_value(&pyvalue)?;
-        }
-        map.end()
+    fn serialize_with_no_extra<S>(
+        &self,
+        serializer: S,
+        dict: *mut pyo3::ffi::PyObject,
+        fields: Option<NonNull<pyo3::ffi::PyObject>>,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_items(serializer, PyDictIter::from_pyobject(dict), fields)
     }
 
     fn serialize_with_extra<S>(
@@ -124,28 +298,83 @@ impl PydanticModel<'_> {
         serializer: S,
         dict: *mut pyo3::ffi::PyObject,
         extra_dict: *mut pyo3::ffi::PyObject,
+        fields: Option<NonNull<pyo3::ffi::PyObject>>,
     ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let iter = PyDictIter::from_pyobject(dict).chain(PyDictIter::from_pyobject(extra_dict));
+        self.serialize_items(serializer, iter, fields)
+    }
+
+    fn serialize_items<S, I>(
+        &self,
+        serializer: S,
+        iter: I,
+        fields: Option<NonNull<pyo3::ffi::PyObject>>,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        I: Iterator<Item = (NonNull<pyo3::ffi::PyObject>, NonNull<pyo3::ffi::PyObject>)>,
+    {
         let len = iter.size_hint().0;
-        if unlikely(len == 0) {
+        if unlikely(len == 0 && fields.is_none()) {
             return serializer.serialize_map(Some(0))?.end();
         }
         let mut items: SmallVec<[(&str, *mut pyo3::ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
+        let mut computed_values = ComputedValues(SmallVec::new());
         for (key, value) in iter {
             if unlikely(ob_type!(key.as_ptr()) != &raw mut pyo3::ffi::PyUnicode_Type) {
                 return Err(serde::ser::Error::custom(KEY_MUST_BE_STR));
             }
-            let key_as_str = unicode_to_str(key.as_ptr()).map_err(serde::ser::Error::custom)?;
+            let mut key_as_str = unicode_to_str(key.as_ptr()).map_err(serde::ser::Error::custom)?;
             if unlikely(key_as_str.as_bytes()[0] == b'_') {
                 continue;
             }
+            if let Some(fields) = fields {
+                if self.opts & PYDANTIC_EXCLUDE != 0 {
+                    let excluded = unsafe { pytuple_get_item(fields.as_ptr(), 1) };
+                    if unsafe { pyo3::ffi::PySet_Contains(excluded, key.as_ptr()) } == 1 {
+                        continue;
+                    }
+                }
+                if self.opts & PYDANTIC_BY_ALIAS != 0 {
+                    let aliases = unsafe { pytuple_get_item(fields.as_ptr(), 0) };
+                    let alias = unsafe { pyo3::ffi::PyDict_GetItem(aliases, key.as_ptr()) };
+                    if !alias.is_null() {
+                        key_as_str = unicode_to_str(alias).map_err(serde::ser::Error::custom)?;
+                    }
+                }
+            }
             items.push((key_as_str, value.as_ptr()));
         }
 
+        if let Some(fields) = fields {
+            if self.opts & PYDANTIC_COMPUTED_FIELDS != 0 {
+                let computed = unsafe { pytuple_get_item(fields.as_ptr(), 2) };
+                for i in 0..unsafe { pyo3::ffi::Py_SIZE(computed) } {
+                    let field = unsafe { pytuple_get_item(computed, i) };
+                    let name = unsafe { pytuple_get_item(field, 0) };
+                    let value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, name) };
+                    if unlikely(value.is_null()) {
+                        let name = unicode_to_str(name).unwrap_or_default();
+                        return Err(serde::ser::Error::custom(format!(
+                            "Pydantic computed field raised an exception: {name}"
+                        )));
+                    }
+                    computed_values.0.push(value);
+                    let key = if self.opts & PYDANTIC_BY_ALIAS != 0 {
+                        unsafe { pytuple_get_item(field, 1) }
+                    } else {
+                        name
+                    };
+                    let key_as_str = unicode_to_str(key).map_err(serde::ser::Error::custom)?;
+                    items.push((key_as_str, value));
+                }
+            }
+        }
+
         if self.opts & SORT_KEYS != 0 {
             items.sort_unstable_by(|a, b| a.0.cmp(b.0));
         }
@@ -3,19 +3,29 @@
 use crate::ffi::*;
 use crate::msgpack;
 use crate::opt::*;
+use crate::serialize::attrs::*;
+use crate::serialize::bigint::*;
 use crate::serialize::bytearray::*;
 use crate::serialize::bytes::*;
+use crate::serialize::collection::*;
 use crate::serialize::dataclass::*;
 use crate::serialize::datetime::*;
+use crate::serialize::decimal::*;
 use crate::serialize::default::*;
 use crate::serialize::dict::*;
+use crate::serialize::enumeration::*;
 use crate::serialize::ext::*;
 use crate::serialize::fragment::*;
+use crate::serialize::iterable::*;
 use crate::serialize::list::*;
 use crate::serialize::memoryview::*;
+use crate::serialize::namedtuple::*;
 use crate::serialize::numpy::*;
+use crate::serialize::protocol::*;
 use crate::serialize::pydantic::*;
+use crate::serialize::set::*;
 use crate::serialize::str::*;
+use crate::serialize::timestamp::*;
 use crate::serialize::tuple::*;
 use crate::serialize::uuid::*;
 use crate::serialize::writer::*;
@@ -28,10 +38,12 @@ pub fn serialize(
     ptr: *mut pyo3::ffi::PyObject,
     state: *mut State,
     default: Option<NonNull<pyo3::ffi::PyObject>>,
+    ext_types: Option<NonNull<pyo3::ffi::PyObject>>,
     opts: Opt,
+    ext_tags: ExtTags,
 ) -> Result<NonNull<pyo3::ffi::PyObject>, String> {
     let mut buf = BytesWriter::default();
-    let default_hook = DefaultHook::new(default);
+    let default_hook = DefaultHook::new(default, ext_types, ext_tags);
     let obj = PyObject::new(ptr, state, opts, &default_hook);
     let mut ser = msgpack::Serializer::new(&mut buf);
     let res = obj.serialize(&mut ser);
@@ -85,6 +97,44 @@ impl<'a> PyObject<'a> {
         res
     }
 
+    fn serialize_with_msgpack_method<S>(
+        &self,
+        method: NonNull<pyo3::ffi::PyObject>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let res = self.default.enter_method_call(method.as_ptr(), self.ptr);
+        unsafe { pyo3::ffi::Py_DECREF(method.as_ptr()) };
+        let obj = res.map_err(serde::ser::Error::custom)?;
+        let res = PyObject::new(obj, self.state, self.opts, self.default).serialize(serializer);
+        self.default.leave_call();
+        unsafe { pyo3::ffi::Py_DECREF(obj) };
+        res
+    }
+
+    /// Serialize a new reference to the conversion of a numpy array, such as
+    /// the scalar of a 0-dimensional array.
+    fn serialize_numpy_conversion<S>(
+        &self,
+        obj: *mut pyo3::ffi::PyObject,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if obj.is_null() {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(serde::ser::Error::custom(
+                "numpy array could not be converted",
+            ));
+        }
+        let res = PyObject::new(obj, self.state, self.opts, self.default).serialize(serializer);
+        unsafe { pyo3::ffi::Py_DECREF(obj) };
+        res
+    }
+
     #[inline(never)]
     fn serialize_unlikely<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -95,7 +145,12 @@ impl<'a> PyObject<'a> {
         if self.opts & PASSTHROUGH_DATETIME == 0 {
             let datetime_api = unsafe { *pyo3::ffi::PyDateTimeAPI() };
             if ob_type == datetime_api.DateTimeType {
-                match DateTime::new(self.ptr, self.state, self.opts) {
+                match DateTime::new(
+                    self.ptr,
+                    self.state,
+                    self.opts,
+                    self.default.ext_tags.datetime,
+                ) {
                     Ok(val) => return val.serialize(serializer),
                     Err(err) => return Err(serde::ser::Error::custom(err)),
                 }
@@ -111,20 +166,59 @@ impl<'a> PyObject<'a> {
             }
         }
 
+        if self.opts
+            & (SERIALIZE_TIMEDELTA
+                | TIMEDELTA_AS_EXT
+                | TIMEDELTA_AS_MICROSECONDS
+                | TIMEDELTA_AS_SECONDS)
+            != 0
+            && ob_type == unsafe { (*pyo3::ffi::PyDateTimeAPI()).DeltaType }
+        {
+            return Timedelta::new(self.ptr, self.opts, self.default.ext_tags.timedelta)
+                .serialize(serializer);
+        }
+
         if self.opts & PASSTHROUGH_TUPLE == 0 && ob_type == &raw mut pyo3::ffi::PyTuple_Type {
             return Tuple::new(self.ptr, self.state, self.opts, self.default).serialize(serializer);
         }
 
+        if self.opts & NAMEDTUPLE_AS_MAP != 0
+            && is_subclass(ob_type, pyo3::ffi::Py_TPFLAGS_TUPLE_SUBCLASS)
+        {
+            if let Some(fields) = namedtuple_fields(ob_type, self.state) {
+                return NamedTuple::new(self.ptr, fields, self.state, self.opts, self.default)
+                    .serialize(serializer);
+            }
+        }
+
+        if self.opts & (SERIALIZE_SETS | SORT_SETS) != 0
+            && (ob_type == &raw mut pyo3::ffi::PySet_Type
+                || ob_type == &raw mut pyo3::ffi::PyFrozenSet_Type)
+        {
+            return Set::new(self.ptr, self.state, self.opts, self.default).serialize(serializer);
+        }
+
         if self.opts & PASSTHROUGH_UUID == 0 && ob_type == unsafe { (*self.state).uuid_type } {
             return UUID::new(self.ptr, self.state).serialize(serializer);
         }
 
+        if self.opts & (SERIALIZE_DECIMAL | DECIMAL_AS_EXT) != 0
+            && ob_type == unsafe { (*self.state).decimal_type }
+        {
+            return Decimal::new(
+                self.ptr,
+                self.state,
+                self.opts,
+                self.default.ext_tags.decimal,
+            )
+            .serialize(serializer);
+        }
+
         if ob_type!(ob_type) == unsafe { (*self.state).enum_type } {
             if self.opts & PASSTHROUGH_ENUM == 0 {
-                let value =
-                    unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).value_str) };
-                unsafe { pyo3::ffi::Py_DECREF(value) };
-                return PyObject::new(value, self.state, self.opts, self.default)
+                let member = EnumMember::new(self.ptr, self.state, self.opts)
+                    .map_err(serde::ser::Error::custom)?;
+                return PyObject::new(member.as_ptr(), self.state, self.opts, self.default)
                     .serialize(serializer);
             } else {
                 return self.serialize_with_default_hook(serializer);
@@ -141,6 +235,13 @@ impl<'a> PyObject<'a> {
                     Err(err) => {
                         if self.opts & PASSTHROUGH_BIG_INT != 0 {
                             return self.serialize_with_default_hook(serializer);
+                        } else if self.opts & BIG_INT_AS_EXT != 0 {
+                            return BigInt::new(
+                                self.ptr,
+                                self.state,
+                                self.default.ext_tags.big_int,
+                            )
+                            .serialize(serializer);
                         } else {
                             return Err(serde::ser::Error::custom(err));
                         }
@@ -155,27 +256,58 @@ impl<'a> PyObject<'a> {
                 return Dict::new(self.ptr, self.state, self.opts, self.default)
                     .serialize(serializer);
             }
+            if self.opts & (SERIALIZE_SETS | SORT_SETS) != 0
+                && unsafe { pyo3::ffi::PyAnySet_Check(self.ptr) } != 0
+            {
+                return Set::new(self.ptr, self.state, self.opts, self.default)
+                    .serialize(serializer);
+            }
         }
 
         if ob_type == unsafe { (*self.state).ext_type } {
             return Ext::new(self.ptr).serialize(serializer);
         }
 
+        if ob_type == unsafe { (*self.state).timestamp_type } {
+            return Timestamp::new(self.ptr).serialize(serializer);
+        }
+
+        if let Some(entry) = self.default.ext_type(ob_type) {
+            return RegisteredExt::new(self.ptr, entry).serialize(serializer);
+        }
+
+        if let Some(method) = msgpack_method(ob_type, self.state) {
+            return self.serialize_with_msgpack_method(method, serializer);
+        }
+
         if self.opts & PASSTHROUGH_DATACLASS == 0 && is_dataclass(ob_type, self.state) {
             return Dataclass::new(self.ptr, self.state, self.opts, self.default)
                 .serialize(serializer);
         }
 
+        if self.opts & PASSTHROUGH_ATTRS == 0 && is_attrs(ob_type, self.state) {
+            return Attrs::new(self.ptr, self.state, self.opts, self.default).serialize(serializer);
+        }
+
         if self.opts & SERIALIZE_PYDANTIC != 0 && is_pydantic_model(ob_type, self.state) {
             return PydanticModel::new(self.ptr, self.state, self.opts, self.default)
                 .serialize(serializer);
         }
 
-        if self.opts & SERIALIZE_NUMPY != 0 {
+        if self.opts & (SERIALIZE_NUMPY | NUMPY_AS_EXT) != 0 {
             if let Some(numpy_types_ref) = unsafe { (*self.state).get_numpy_types() } {
                 if ob_type == numpy_types_ref.bool_ {
                     return NumpyBool::new(self.ptr).serialize(serializer);
                 }
+                if ob_type == numpy_types_ref.bytes_ {
+                    return Bytes::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.complex64 {
+                    return NumpyComplex64::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.complex128 {
+                    return NumpyComplex128::new(self.ptr).serialize(serializer);
+                }
                 if ob_type == numpy_types_ref.datetime64 {
                     return NumpyDatetime64::new(self.ptr, self.state, self.opts)
                         .serialize(serializer);
@@ -201,6 +333,34 @@ impl<'a> PyObject<'a> {
                 if ob_type == numpy_types_ref.int64 {
                     return NumpyInt64::new(self.ptr).serialize(serializer);
                 }
+                if ob_type == numpy_types_ref.intc {
+                    return NumpyIntc::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.longdouble {
+                    return NumpyLongDouble::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.longlong {
+                    return NumpyLonglong::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.str_ {
+                    return StrSubclass::new(self.ptr, self.opts).serialize(serializer);
+                }
+                if self.opts
+                    & (SERIALIZE_TIMEDELTA
+                        | TIMEDELTA_AS_EXT
+                        | TIMEDELTA_AS_MICROSECONDS
+                        | TIMEDELTA_AS_SECONDS)
+                    != 0
+                    && ob_type == numpy_types_ref.timedelta64
+                {
+                    return NumpyTimedelta64::new(
+                        self.ptr,
+                        self.state,
+                        self.opts,
+                        self.default.ext_tags.timedelta,
+                    )
+                    .serialize(serializer);
+                }
                 if ob_type == numpy_types_ref.uint8 {
                     return NumpyUint8::new(self.ptr).serialize(serializer);
                 }
@@ -213,16 +373,54 @@ impl<'a> PyObject<'a> {
                 if ob_type == numpy_types_ref.uint64 {
                     return NumpyUint64::new(self.ptr).serialize(serializer);
                 }
+                if ob_type == numpy_types_ref.uintc {
+                    return NumpyUintc::new(self.ptr).serialize(serializer);
+                }
+                if ob_type == numpy_types_ref.ulonglong {
+                    return NumpyUlonglong::new(self.ptr).serialize(serializer);
+                }
                 if ob_type == numpy_types_ref.array {
-                    match NumpyArray::new(self.ptr, self.state, self.opts) {
+                    if self.opts & NUMPY_AS_EXT != 0 {
+                        if let Some(val) =
+                            NumpyArrayExt::new(self.ptr, self.state, self.default.ext_tags.numpy)
+                        {
+                            return val.serialize(serializer);
+                        }
+                    }
+                    match NumpyArray::new(
+                        self.ptr,
+                        self.state,
+                        self.opts,
+                        self.default.ext_tags.timedelta,
+                    ) {
                         Ok(val) => return val.serialize(serializer),
+                        Err(PyArrayError::LongDouble) => {
+                            let obj = unsafe {
+                                pyo3::ffi::PyObject_CallMethodOneArg(
+                                    self.ptr,
+                                    (*self.state).astype_str,
+                                    numpy_types_ref.float64.cast(),
+                                )
+                            };
+                            return self.serialize_numpy_conversion(obj, serializer);
+                        }
+                        Err(PyArrayError::ZeroDimensional) => {
+                            let obj = unsafe {
+                                let key = pyo3::ffi::PyTuple_New(0);
+                                let obj = pyo3::ffi::PyObject_GetItem(self.ptr, key);
+                                pyo3::ffi::Py_DECREF(key);
+                                obj
+                            };
+                            return self.serialize_numpy_conversion(obj, serializer);
+                        }
                         Err(PyArrayError::Malformed) => {
                             return Err(serde::ser::Error::custom("numpy array is malformed"))
                         }
-                        Err(PyArrayError::NotContiguous)
-                        | Err(PyArrayError::UnsupportedDataType) => {
+                        Err(PyArrayError::UnsupportedDataType) => {
                             if self.default.inner.is_none() {
-                                return Err(serde::ser::Error::custom("numpy array is not C contiguous; use ndarray.tolist() in default"));
+                                return Err(serde::ser::Error::custom(
+                                    "unsupported datatype in numpy array",
+                                ));
                             }
                         }
                     }
@@ -242,6 +440,22 @@ impl<'a> PyObject<'a> {
             return Fragment::new(self.ptr).serialize(serializer);
         }
 
+        if self.opts & SERIALIZE_COLLECTIONS != 0 {
+            if is_mapping(ob_type) {
+                return Mapping::new(self.ptr, self.state, self.opts, self.default)
+                    .serialize(serializer);
+            }
+            if is_sequence(ob_type) {
+                return Sequence::new(self.ptr, self.state, self.opts, self.default)
+                    .serialize(serializer);
+            }
+        }
+
+        if self.opts & SERIALIZE_ITERABLES != 0 && is_iterable(ob_type) {
+            return Iterable::new(self.ptr, self.state, self.opts, self.default)
+                .serialize(serializer);
+        }
+
         self.serialize_with_default_hook(serializer)
     }
 }
@@ -262,6 +476,9 @@ impl Serialize for PyObject<'_> {
                 Err(err) => {
                     if self.opts & PASSTHROUGH_BIG_INT != 0 {
                         self.serialize_with_default_hook(serializer)
+                    } else if self.opts & BIG_INT_AS_EXT != 0 {
+                        BigInt::new(self.ptr, self.state, self.default.ext_tags.big_int)
+                            .serialize(serializer)
                     } else {
                         Err(serde::ser::Error::custom(err))
                     }
@@ -287,14 +504,21 @@ pub struct DictTupleKey {
     ptr: *mut pyo3::ffi::PyObject,
     state: *mut State,
     opts: Opt,
+    ext_tags: ExtTags,
 }
 
 impl DictTupleKey {
-    pub fn new(ptr: *mut pyo3::ffi::PyObject, state: *mut State, opts: Opt) -> Self {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        ext_tags: ExtTags,
+    ) -> Self {
         DictTupleKey {
             ptr: ptr,
             state: state,
             opts: opts,
+            ext_tags: ext_tags,
         }
     }
 }
@@ -309,7 +533,7 @@ impl Serialize for DictTupleKey {
         let mut seq = serializer.serialize_seq(Some(len))?;
         for i in 0..len {
             let item = unsafe { pytuple_get_item(self.ptr, i as isize) };
-            let value = DictKey::new(item, self.state, self.opts);
+            let value = DictKey::new(item, self.state, self.opts, self.ext_tags);
             seq.serialize_element(&value)?;
         }
         seq.end()
@@ -320,14 +544,21 @@ pub struct DictKey {
     ptr: *mut pyo3::ffi::PyObject,
     state: *mut State,
     opts: Opt,
+    ext_tags: ExtTags,
 }
 
 impl DictKey {
-    pub fn new(ptr: *mut pyo3::ffi::PyObject, state: *mut State, opts: Opt) -> Self {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        ext_tags: ExtTags,
+    ) -> Self {
         DictKey {
             ptr: ptr,
             state: state,
             opts: opts,
+            ext_tags: ext_tags,
         }
     }
 
@@ -340,7 +571,7 @@ impl DictKey {
 
         let datetime_api = unsafe { *pyo3::ffi::PyDateTimeAPI() };
         if ob_type == datetime_api.DateTimeType {
-            match DateTime::new(self.ptr, self.state, self.opts) {
+            match DateTime::new(self.ptr, self.state, self.opts, self.ext_tags.datetime) {
                 Ok(val) => return val.serialize(serializer),
                 Err(err) => return Err(serde::ser::Error::custom(err)),
             }
@@ -356,17 +587,23 @@ impl DictKey {
         }
 
         if ob_type == &raw mut pyo3::ffi::PyTuple_Type {
-            return DictTupleKey::new(self.ptr, self.state, self.opts).serialize(serializer);
+            return DictTupleKey::new(self.ptr, self.state, self.opts, self.ext_tags)
+                .serialize(serializer);
         }
 
         if ob_type == unsafe { (*self.state).uuid_type } {
             return UUID::new(self.ptr, self.state).serialize(serializer);
         }
 
+        if ob_type == unsafe { (*self.state).timestamp_type } {
+            return Timestamp::new(self.ptr).serialize(serializer);
+        }
+
         if ob_type!(ob_type) == unsafe { (*self.state).enum_type } {
-            let value = unsafe { pyo3::ffi::PyObject_GetAttr(self.ptr, (*self.state).value_str) };
-            unsafe { pyo3::ffi::Py_DECREF(value) };
-            return DictKey::new(value, self.state, self.opts).serialize(serializer);
+            let member = EnumMember::new(self.ptr, self.state, self.opts)
+                .map_err(serde::ser::Error::custom)?;
+            return DictKey::new(member.as_ptr(), self.state, self.opts, self.ext_tags)
+                .serialize(serializer);
         }
 
         if is_subclass(ob_type, pyo3::ffi::Py_TPFLAGS_UNICODE_SUBCLASS) {
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::exc::*;
+use crate::ffi::*;
+use crate::msgpack;
+use crate::opt::*;
+use crate::serialize::default::DefaultHook;
+use crate::serialize::fragment::EncodedItem;
+use crate::serialize::serializer::*;
+use crate::state::State;
+use crate::util::unlikely;
+
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+const SET_CHANGED_SIZE: &str = "set changed size during iteration";
+
+pub struct Set<'a> {
+    ptr: *mut pyo3::ffi::PyObject,
+    state: *mut State,
+    opts: Opt,
+    default: &'a DefaultHook,
+}
+
+impl<'a> Set<'a> {
+    pub fn new(
+        ptr: *mut pyo3::ffi::PyObject,
+        state: *mut State,
+        opts: Opt,
+        default: &'a DefaultHook,
+    ) -> Self {
+        Set {
+            ptr: ptr,
+            state: state,
+            opts: opts,
+            default: default,
+        }
+    }
+}
+
+impl Serialize for Set<'_> {
+    #[inline(never)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut critical_section = CriticalSection::new();
+        critical_section.begin(self.ptr);
+        if self.opts & SORT_SETS != 0 {
+            self.serialize_sorted(serializer)
+        } else {
+            self.serialize_unsorted(serializer)
+        }
+    }
+}
+
+impl Set<'_> {
+    #[inline(always)]
+    fn serialize_unsorted<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = unsafe { pyo3::ffi::PySet_Size(self.ptr) } as usize;
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        let mut count = 0;
+        for item in unsafe { PyIter::from_pyobject(self.ptr).unwrap_unchecked() } {
+            if unlikely(count == len) {
+                return Err(serde::ser::Error::custom(SET_CHANGED_SIZE));
+            }
+            let value = PyObject::new(item.as_ptr(), self.state, self.opts, self.default);
+            seq.serialize_element(&value)?;
+            count += 1;
+        }
+        if unlikely(count != len || !unsafe { pyo3::ffi::PyErr_Occurred() }.is_null()) {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(serde::ser::Error::custom(SET_CHANGED_SIZE));
+        }
+        seq.end()
+    }
+
+    #[inline(never)]
+    fn serialize_sorted<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if unlikely(!self.default.enter_nested()) {
+            return Err(serde::ser::Error::custom(RECURSION_LIMIT_REACHED));
+        }
+        let items = self.encode_items();
+        self.default.leave_nested();
+        let mut items = items.map_err(serde::ser::Error::custom)?;
+        items.sort_unstable();
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items.iter() {
+            seq.serialize_element(&EncodedItem(item))?;
+        }
+        seq.end()
+    }
+
+    fn encode_items(&self) -> Result<Vec<Vec<u8>>, String> {
+        let len = unsafe { pyo3::ffi::PySet_Size(self.ptr) } as usize;
+        let mut items: Vec<Vec<u8>> = Vec::with_capacity(len);
+        for item in unsafe { PyIter::from_pyobject(self.ptr).unwrap_unchecked() } {
+            let mut buffer: Vec<u8> = Vec::new();
+            let value = PyObject::new(item.as_ptr(), self.state, self.opts, self.default);
+            value
+                .serialize(&mut msgpack::Serializer::new(&mut buffer))
+                .map_err(|err| err.to_string())?;
+            items.push(buffer);
+        }
+        if unlikely(items.len() != len || !unsafe { pyo3::ffi::PyErr_Occurred() }.is_null()) {
+            unsafe { pyo3::ffi::PyErr_Clear() };
+            return Err(SET_CHANGED_SIZE.to_string());
+        }
+        Ok(items)
+    }
+}
@@ -3,6 +3,8 @@
 use crate::deserialize::KeyMap;
 use crate::ext::create_ext_type;
 use crate::fragment::create_fragment_type;
+use crate::serialize::TypeMap;
+use crate::timestamp::create_timestamp_type;
 use pyo3::ffi::*;
 use std::ffi::CStr;
 use std::ptr::null_mut;
@@ -10,6 +12,7 @@ use std::sync::OnceLock;
 
 pub struct NumpyTypes {
     pub array: *mut PyTypeObject,
+    pub frombuffer: *mut PyObject,
     pub float64: *mut PyTypeObject,
     pub float32: *mut PyTypeObject,
     pub float16: *mut PyTypeObject,
@@ -23,6 +26,16 @@ pub struct NumpyTypes {
     pub uint8: *mut PyTypeObject,
     pub bool_: *mut PyTypeObject,
     pub datetime64: *mut PyTypeObject,
+    pub bytes_: *mut PyTypeObject,
+    pub complex64: *mut PyTypeObject,
+    pub complex128: *mut PyTypeObject,
+    pub intc: *mut PyTypeObject,
+    pub longdouble: *mut PyTypeObject,
+    pub longlong: *mut PyTypeObject,
+    pub str_: *mut PyTypeObject,
+    pub timedelta64: *mut PyTypeObject,
+    pub uintc: *mut PyTypeObject,
+    pub ulonglong: *mut PyTypeObject,
 }
 
 #[inline]
@@ -30,6 +43,14 @@ unsafe fn get_type(module_dict: *mut PyObject, type_name: &CStr) -> *mut PyTypeO
     PyMapping_GetItemString(module_dict, type_name.as_ptr()).cast::<PyTypeObject>()
 }
 
+#[cold]
+unsafe fn load_object(module_name: &CStr, name: &CStr) -> *mut PyObject {
+    let module = PyImport_ImportModule(module_name.as_ptr());
+    let ptr = PyObject_GetAttrString(module, name.as_ptr());
+    Py_DECREF(module);
+    ptr
+}
+
 #[cold]
 unsafe fn load_type(module_name: &CStr, type_name: &CStr) -> *mut PyTypeObject {
     let module = PyImport_ImportModule(module_name.as_ptr());
@@ -52,6 +73,7 @@ fn load_numpy_types() -> Option<NumpyTypes> {
         let numpy_dict = PyObject_GenericGetDict(numpy, null_mut());
         let types = NumpyTypes {
             array: get_type(numpy_dict, c"ndarray"),
+            frombuffer: PyMapping_GetItemString(numpy_dict, c"frombuffer".as_ptr()),
             float16: get_type(numpy_dict, c"half"),
             float32: get_type(numpy_dict, c"float32"),
             float64: get_type(numpy_dict, c"float64"),
@@ -65,6 +87,16 @@ fn load_numpy_types() -> Option<NumpyTypes> {
             uint8: get_type(numpy_dict, c"uint8"),
             bool_: get_type(numpy_dict, c"bool_"),
             datetime64: get_type(numpy_dict, c"datetime64"),
+            bytes_: get_type(numpy_dict, c"bytes_"),
+            complex64: get_type(numpy_dict, c"complex64"),
+            complex128: get_type(numpy_dict, c"complex128"),
+            intc: get_type(numpy_dict, c"intc"),
+            longdouble: get_type(numpy_dict, c"longdouble"),
+            longlong: get_type(numpy_dict, c"longlong"),
+            str_: get_type(numpy_dict, c"str_"),
+            timedelta64: get_type(numpy_dict, c"timedelta64"),
+            uintc: get_type(numpy_dict, c"uintc"),
+            ulonglong: get_type(numpy_dict, c"ulonglong"),
         };
         Py_DECREF(numpy_dict);
         Py_DECREF(numpy);
@@ -76,31 +108,72 @@ fn load_numpy_types() -> Option<NumpyTypes> {
 #[repr(C)]
 pub struct State {
     numpy_types: OnceLock<Option<NumpyTypes>>,
+    zoneinfo_type: OnceLock<*mut PyTypeObject>,
     pub dataclass_field_type: *mut PyTypeObject,
+    pub dataclass_missing: *mut PyObject,
+    pub decimal_type: *mut PyTypeObject,
     pub enum_type: *mut PyTypeObject,
     pub ext_type: *mut PyTypeObject,
     pub fragment_type: *mut PyTypeObject,
+    pub timestamp_type: *mut PyTypeObject,
     pub uuid_type: *mut PyTypeObject,
+    pub alias_str: *mut PyObject,
     pub array_struct_str: *mut PyObject,
+    pub as_tuple_str: *mut PyObject,
+    pub astype_str: *mut PyObject,
+    pub attrs_attrs_str: *mut PyObject,
+    pub big_int_tag_str: *mut PyObject,
+    pub bit_length_str: *mut PyObject,
+    pub computed_fields_str: *mut PyObject,
     pub dataclass_fields_str: *mut PyObject,
+    pub datetime_tag_str: *mut PyObject,
+    pub decimal_tag_str: *mut PyObject,
+    pub default_factory_str: *mut PyObject,
     pub default_str: *mut PyObject,
     pub descr_str: *mut PyObject,
     pub dict_str: *mut PyObject,
     pub dtype_str: *mut PyObject,
+    pub exclude_str: *mut PyObject,
     pub ext_hook_str: *mut PyObject,
+    pub ext_types_str: *mut PyObject,
     pub field_type_str: *mut PyObject,
     pub fields_str: *mut PyObject,
+    pub fromutc_str: *mut PyObject,
+    pub info_str: *mut PyObject,
+    pub init_str: *mut PyObject,
     pub int_str: *mut PyObject,
+    pub key_str: *mut PyObject,
+    pub metadata_str: *mut PyObject,
+    pub model_fields_str: *mut PyObject,
+    pub msgpack_key_str: *mut PyObject,
+    pub msgpack_str: *mut PyObject,
+    pub name_str: *mut PyObject,
+    pub namedtuple_fields_str: *mut PyObject,
     pub normalize_str: *mut PyObject,
+    pub numpy_tag_str: *mut PyObject,
     pub option_str: *mut PyObject,
+    pub pydantic_decorators_str: *mut PyObject,
     pub pydantic_extra_str: *mut PyObject,
     pub pydantic_validator_str: *mut PyObject,
+    pub reshape_str: *mut PyObject,
+    pub serialization_alias_str: *mut PyObject,
+    pub skip_str: *mut PyObject,
     pub slots_str: *mut PyObject,
+    pub str_str: *mut PyObject,
+    pub timedelta_tag_str: *mut PyObject,
+    pub type_str: *mut PyObject,
+    pub tzinfo_str: *mut PyObject,
     pub utcoffset_str: *mut PyObject,
     pub value_str: *mut PyObject,
     pub MsgpackEncodeError: *mut PyObject,
     pub MsgpackDecodeError: *mut PyObject,
     pub key_map: KeyMap<512>,
+    pub dataclass_plans: TypeMap<64>,
+    pub enum_members: TypeMap<64>,
+    pub msgpack_methods: TypeMap<64>,
+    pub namedtuple_fields: TypeMap<64>,
+    pub pydantic_fields: TypeMap<64>,
+    pub struct_layouts: TypeMap<64>,
 }
 
 impl State {
@@ -109,33 +182,78 @@ impl State {
         unsafe {
             Self {
                 numpy_types: OnceLock::new(),
+                zoneinfo_type: OnceLock::new(),
                 dataclass_field_type: load_type(c"dataclasses", c"_FIELD"),
+                dataclass_missing: load_object(c"dataclasses", c"MISSING"),
+                decimal_type: load_type(c"decimal", c"Decimal"),
                 enum_type: load_type(c"enum", c"EnumMeta"),
                 ext_type: create_ext_type(),
                 fragment_type: create_fragment_type(),
+                timestamp_type: create_timestamp_type(),
                 uuid_type: load_type(c"uuid", c"UUID"),
+                alias_str: PyUnicode_InternFromString(c"alias".as_ptr()),
                 array_struct_str: PyUnicode_InternFromString(c"__array_struct__".as_ptr()),
+                as_tuple_str: PyUnicode_InternFromString(c"as_tuple".as_ptr()),
+                astype_str: PyUnicode_InternFromString(c"astype".as_ptr()),
+                attrs_attrs_str: PyUnicode_InternFromString(c"__attrs_attrs__".as_ptr()),
+                big_int_tag_str: PyUnicode_InternFromString(c"big_int_tag".as_ptr()),
+                bit_length_str: PyUnicode_InternFromString(c"bit_length".as_ptr()),
+                computed_fields_str: PyUnicode_InternFromString(c"computed_fields".as_ptr()),
                 dataclass_fields_str: PyUnicode_InternFromString(c"__dataclass_fields__".as_ptr()),
+                datetime_tag_str: PyUnicode_InternFromString(c"datetime_tag".as_ptr()),
+                decimal_tag_str: PyUnicode_InternFromString(c"decimal_tag".as_ptr()),
+                default_factory_str: PyUnicode_InternFromString(c"default_factory".as_ptr()),
                 default_str: PyUnicode_InternFromString(c"default".as_ptr()),
                 descr_str: PyUnicode_InternFromString(c"descr".as_ptr()),
                 dict_str: PyUnicode_InternFromString(c"__dict__".as_ptr()),
                 dtype_str: PyUnicode_InternFromString(c"dtype".as_ptr()),
+                exclude_str: PyUnicode_InternFromString(c"exclude".as_ptr()),
                 ext_hook_str: PyUnicode_InternFromString(c"ext_hook".as_ptr()),
+                ext_types_str: PyUnicode_InternFromString(c"ext_types".as_ptr()),
                 field_type_str: PyUnicode_InternFromString(c"_field_type".as_ptr()),
                 fields_str: PyUnicode_InternFromString(c"__fields__".as_ptr()),
+                fromutc_str: PyUnicode_InternFromString(c"fromutc".as_ptr()),
+                info_str: PyUnicode_InternFromString(c"info".as_ptr()),
+                init_str: PyUnicode_InternFromString(c"init".as_ptr()),
                 int_str: PyUnicode_InternFromString(c"int".as_ptr()),
+                key_str: PyUnicode_InternFromString(c"key".as_ptr()),
+                metadata_str: PyUnicode_InternFromString(c"metadata".as_ptr()),
+                model_fields_str: PyUnicode_InternFromString(c"model_fields".as_ptr()),
+                msgpack_key_str: PyUnicode_InternFromString(c"msgpack".as_ptr()),
+                msgpack_str: PyUnicode_InternFromString(c"__msgpack__".as_ptr()),
+                name_str: PyUnicode_InternFromString(c"name".as_ptr()),
+                namedtuple_fields_str: PyUnicode_InternFromString(c"_fields".as_ptr()),
                 normalize_str: PyUnicode_InternFromString(c"normalize".as_ptr()),
+                numpy_tag_str: PyUnicode_InternFromString(c"numpy_tag".as_ptr()),
                 option_str: PyUnicode_InternFromString(c"option".as_ptr()),
+                pydantic_decorators_str: PyUnicode_InternFromString(
+                    c"__pydantic_decorators__".as_ptr(),
+                ),
                 pydantic_extra_str: PyUnicode_InternFromString(c"__pydantic_extra__".as_ptr()),
                 pydantic_validator_str: PyUnicode_InternFromString(
                     c"__pydantic_validator__".as_ptr(),
                 ),
+                reshape_str: PyUnicode_InternFromString(c"reshape".as_ptr()),
+                serialization_alias_str: PyUnicode_InternFromString(
+                    c"serialization_alias".as_ptr(),
+                ),
+                skip_str: PyUnicode_InternFromString(c"skip".as_ptr()),
                 slots_str: PyUnicode_InternFromString(c"__slots__".as_ptr()),
+                str_str: PyUnicode_InternFromString(c"str".as_ptr()),
+                timedelta_tag_str: PyUnicode_InternFromString(c"timedelta_tag".as_ptr()),
+                type_str: PyUnicode_InternFromString(c"type".as_ptr()),
+                tzinfo_str: PyUnicode_InternFromString(c"tzinfo".as_ptr()),
                 utcoffset_str: PyUnicode_InternFromString(c"utcoffset".as_ptr()),
                 value_str: PyUnicode_InternFromString(c"value".as_ptr()),
                 MsgpackEncodeError: Py_NewRef(PyExc_TypeError),
                 MsgpackDecodeError: Py_NewRef(PyExc_ValueError),
                 key_map: KeyMap::new(),
+                dataclass_plans: TypeMap::new(),
+                enum_members: TypeMap::new(),
+                msgpack_methods: TypeMap::new(),
+                namedtuple_fields: TypeMap::new(),
+                pydantic_fields: TypeMap::new(),
+                struct_layouts: TypeMap::new(),
             }
         }
     }
@@ -143,4 +261,10 @@ impl State {
     pub fn get_numpy_types(&self) -> &Option<NumpyTypes> {
         self.numpy_types.get_or_init(load_numpy_types)
     }
+
+    pub fn get_zoneinfo_type(&self) -> *mut PyTypeObject {
+        *self
+            .zoneinfo_type
+            .get_or_init(|| unsafe { load_type(c"zoneinfo", c"ZoneInfo") })
+    }
 }
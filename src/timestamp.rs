@@ -0,0 +1,285 @@
+use chrono::{Datelike, Timelike};
+use pyo3::ffi::*;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::null_mut;
+
+#[repr(C)]
+pub struct PyTimestamp {
+    pub ob_base: PyObject,
+    pub seconds: i64,
+    pub nanoseconds: u32,
+}
+
+pub unsafe fn timestamp_from_value(
+    subtype: *mut PyTypeObject,
+    seconds: i64,
+    nanoseconds: u32,
+) -> *mut PyObject {
+    let obj = (*subtype).tp_alloc.unwrap()(subtype, 0);
+    if !obj.is_null() {
+        (*obj.cast::<PyTimestamp>()).seconds = seconds;
+        (*obj.cast::<PyTimestamp>()).nanoseconds = nanoseconds;
+    }
+    obj
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_new(
+    subtype: *mut PyTypeObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    let mut kwlist: [*const c_char; 3] = [c"seconds".as_ptr(), c"nanoseconds".as_ptr(), null_mut()];
+    let mut seconds: i64 = 0;
+    let mut nanoseconds: i64 = 0;
+    if PyArg_ParseTupleAndKeywords(
+        args,
+        kwds,
+        c"L|L:Timestamp".as_ptr(),
+        kwlist.as_mut_ptr().cast(),
+        &mut seconds,
+        &mut nanoseconds,
+    ) == 0
+    {
+        return null_mut();
+    }
+    if !(0..1_000_000_000).contains(&nanoseconds) {
+        PyErr_SetString(
+            PyExc_ValueError,
+            c"nanoseconds must be in the range [0, 999999999]".as_ptr(),
+        );
+        return null_mut();
+    }
+    timestamp_from_value(subtype, seconds, nanoseconds as u32)
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_dealloc(op: *mut PyObject) {
+    (*ob_type!(op)).tp_free.unwrap()(op.cast::<c_void>());
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_repr(op: *mut PyObject) -> *mut PyObject {
+    let timestamp = op.cast::<PyTimestamp>();
+    PyUnicode_FromFormat(
+        c"Timestamp(seconds=%lld, nanoseconds=%u)".as_ptr(),
+        (*timestamp).seconds,
+        (*timestamp).nanoseconds as c_uint,
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_hash(op: *mut PyObject) -> Py_hash_t {
+    let timestamp = op.cast::<PyTimestamp>();
+    let value = PyTuple_New(2);
+    PyTuple_SET_ITEM(value, 0, PyLong_FromLongLong((*timestamp).seconds));
+    PyTuple_SET_ITEM(
+        value,
+        1,
+        PyLong_FromUnsignedLong((*timestamp).nanoseconds.into()),
+    );
+    let hash = PyObject_Hash(value);
+    Py_DECREF(value);
+    hash
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_richcompare(
+    op: *mut PyObject,
+    other: *mut PyObject,
+    compare_op: c_int,
+) -> *mut PyObject {
+    if PyObject_TypeCheck(other, ob_type!(op)) == 0 && PyObject_TypeCheck(op, ob_type!(other)) == 0
+    {
+        return Py_NewRef(Py_NotImplemented());
+    }
+    let lhs = op.cast::<PyTimestamp>();
+    let rhs = other.cast::<PyTimestamp>();
+    let ordering = ((*lhs).seconds, (*lhs).nanoseconds).cmp(&((*rhs).seconds, (*rhs).nanoseconds));
+    let result = if compare_op == Py_LT {
+        ordering.is_lt()
+    } else if compare_op == Py_LE {
+        ordering.is_le()
+    } else if compare_op == Py_EQ {
+        ordering.is_eq()
+    } else if compare_op == Py_NE {
+        ordering.is_ne()
+    } else if compare_op == Py_GT {
+        ordering.is_gt()
+    } else {
+        ordering.is_ge()
+    };
+    Py_NewRef(if result { Py_True() } else { Py_False() })
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_to_datetime(
+    op: *mut PyObject,
+    _args: *mut PyObject,
+) -> *mut PyObject {
+    let timestamp = op.cast::<PyTimestamp>();
+    let datetime = match chrono::DateTime::<chrono::Utc>::from_timestamp(
+        (*timestamp).seconds,
+        (*timestamp).nanoseconds,
+    ) {
+        Some(value) => value,
+        None => {
+            PyErr_SetString(
+                PyExc_ValueError,
+                c"timestamp out of range for datetime".as_ptr(),
+            );
+            return null_mut();
+        }
+    };
+    let datetime_api = *PyDateTimeAPI();
+    (datetime_api.DateTime_FromDateAndTime)(
+        datetime.year(),
+        datetime.month() as i32,
+        datetime.day() as i32,
+        datetime.hour() as i32,
+        datetime.minute() as i32,
+        datetime.second() as i32,
+        (datetime.nanosecond() / 1000) as i32,
+        datetime_api.TimeZone_UTC,
+        datetime_api.DateTimeType,
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn timestamp_from_datetime(
+    subtype: *mut PyObject,
+    datetime: *mut PyObject,
+) -> *mut PyObject {
+    if PyDateTime_Check(datetime) == 0 {
+        PyErr_SetString(
+            PyExc_TypeError,
+            c"Timestamp.from_datetime() argument must be datetime.datetime".as_ptr(),
+        );
+        return null_mut();
+    }
+    let offset = PyObject_CallMethod(datetime, c"utcoffset".as_ptr(), null_mut());
+    if offset.is_null() {
+        return null_mut();
+    }
+    if offset == Py_None() {
+        Py_DECREF(offset);
+        PyErr_SetString(
+            PyExc_ValueError,
+            c"Timestamp.from_datetime() argument must be an aware datetime".as_ptr(),
+        );
+        return null_mut();
+    }
+    let offset_microseconds = (i64::from(PyDateTime_DELTA_GET_DAYS(offset)) * 86400
+        + i64::from(PyDateTime_DELTA_GET_SECONDS(offset)))
+        * 1_000_000
+        + i64::from(PyDateTime_DELTA_GET_MICROSECONDS(offset));
+    Py_DECREF(offset);
+    let local = chrono::NaiveDate::from_ymd_opt(
+        PyDateTime_GET_YEAR(datetime),
+        PyDateTime_GET_MONTH(datetime) as u32,
+        PyDateTime_GET_DAY(datetime) as u32,
+    )
+    .unwrap()
+    .and_hms_opt(
+        PyDateTime_DATE_GET_HOUR(datetime) as u32,
+        PyDateTime_DATE_GET_MINUTE(datetime) as u32,
+        PyDateTime_DATE_GET_SECOND(datetime) as u32,
+    )
+    .unwrap();
+    let microseconds = local.and_utc().timestamp() * 1_000_000
+        + i64::from(PyDateTime_DATE_GET_MICROSECOND(datetime))
+        - offset_microseconds;
+    timestamp_from_value(
+        subtype.cast::<PyTypeObject>(),
+        microseconds.div_euclid(1_000_000),
+        (microseconds.rem_euclid(1_000_000) * 1000) as u32,
+    )
+}
+
+pub unsafe fn create_timestamp_type() -> *mut PyTypeObject {
+    let methods: Box<[PyMethodDef; 3]> = Box::new([
+        PyMethodDef {
+            ml_name: c"to_datetime".as_ptr(),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: timestamp_to_datetime,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: c"Return the timestamp as an aware UTC datetime.datetime.".as_ptr(),
+        },
+        PyMethodDef {
+            ml_name: c"from_datetime".as_ptr(),
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: timestamp_from_datetime,
+            },
+            ml_flags: METH_O | METH_CLASS,
+            ml_doc: c"Create a timestamp from an aware datetime.datetime.".as_ptr(),
+        },
+        PyMethodDef::zeroed(),
+    ]);
+    let members: Box<[PyMemberDef; 3]> = Box::new([
+        PyMemberDef {
+            name: c"seconds".as_ptr(),
+            type_code: Py_T_LONGLONG,
+            offset: std::mem::offset_of!(PyTimestamp, seconds) as Py_ssize_t,
+            flags: Py_READONLY,
+            doc: c"The seconds since the epoch.".as_ptr(),
+        },
+        PyMemberDef {
+            name: c"nanoseconds".as_ptr(),
+            type_code: Py_T_UINT,
+            offset: std::mem::offset_of!(PyTimestamp, nanoseconds) as Py_ssize_t,
+            flags: Py_READONLY,
+            doc: c"The nanoseconds in the range [0, 999999999].".as_ptr(),
+        },
+        PyMemberDef::default(),
+    ]);
+    let mut slots: [PyType_Slot; 9] = [
+        PyType_Slot {
+            slot: Py_tp_new,
+            pfunc: timestamp_new as *mut c_void,
+        },
+        PyType_Slot {
+            slot: Py_tp_dealloc,
+            pfunc: timestamp_dealloc as *mut c_void,
+        },
+        PyType_Slot {
+            slot: Py_tp_repr,
+            pfunc: timestamp_repr as *mut c_void,
+        },
+        PyType_Slot {
+            slot: Py_tp_hash,
+            pfunc: timestamp_hash as *mut c_void,
+        },
+        PyType_Slot {
+            slot: Py_tp_richcompare,
+            pfunc: timestamp_richcompare as *mut c_void,
+        },
+        PyType_Slot {
+            slot: Py_tp_methods,
+            pfunc: Box::into_raw(methods).cast::<c_void>(),
+        },
+        PyType_Slot {
+            slot: Py_tp_members,
+            pfunc: Box::into_raw(members).cast::<c_void>(),
+        },
+        PyType_Slot {
+            slot: Py_tp_doc,
+            pfunc: c"Timestamp(seconds, nanoseconds=0)\n--\n\nA MessagePack timestamp."
+                .as_ptr()
+                .cast_mut()
+                .cast::<c_void>(),
+        },
+        PyType_Slot {
+            slot: 0,
+            pfunc: null_mut(),
+        },
+    ];
+    let mut spec = PyType_Spec {
+        name: c"ormsgpack.Timestamp".as_ptr(),
+        basicsize: std::mem::size_of::<PyTimestamp>() as c_int,
+        itemsize: 0,
+        flags: Py_TPFLAGS_DEFAULT as c_uint,
+        slots: slots.as_mut_ptr(),
+    };
+    PyType_FromSpec(&mut spec).cast::<PyTypeObject>()
+}
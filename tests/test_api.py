@@ -228,7 +228,13 @@ def test_unpackb_non_interned_kwarg() -> None:
 @pytest.mark.parametrize(
     "option",
     (
-        1 << 16,
+        ormsgpack.OPT_BIN_AS_BYTEARRAY,
+        ormsgpack.OPT_BIN_AS_MEMORYVIEW,
+        ormsgpack.OPT_EXT_AS_MEMORYVIEW,
+        ormsgpack.OPT_INVALID_STR_AS_BYTES,
+        ormsgpack.OPT_INVALID_STR_REPLACE,
+        ormsgpack.OPT_INVALID_STR_SURROGATEESCAPE,
+        1 << 30,
         True,
         -1,
         9223372036854775809,
@@ -242,16 +248,33 @@ def test_packb_invalid_option(option: int) -> None:
 @pytest.mark.parametrize(
     "option",
     (
-        ormsgpack.OPT_NAIVE_UTC,
+        ormsgpack.OPT_ENUM_BY_NAME,
+        ormsgpack.OPT_ENUM_BY_QUALIFIED_NAME,
+        ormsgpack.OPT_NAMEDTUPLE_AS_MAP,
+        ormsgpack.OPT_OMIT_DEFAULTS,
         ormsgpack.OPT_OMIT_MICROSECONDS,
+        ormsgpack.OPT_OMIT_NONE,
+        ormsgpack.OPT_PASSTHROUGH_ATTRS,
         ormsgpack.OPT_PASSTHROUGH_BIG_INT,
         ormsgpack.OPT_PASSTHROUGH_DATACLASS,
         ormsgpack.OPT_PASSTHROUGH_DATETIME,
         ormsgpack.OPT_PASSTHROUGH_SUBCLASS,
         ormsgpack.OPT_PASSTHROUGH_TUPLE,
+        ormsgpack.OPT_PYDANTIC_BY_ALIAS,
+        ormsgpack.OPT_PYDANTIC_COMPUTED_FIELDS,
+        ormsgpack.OPT_PYDANTIC_EXCLUDE,
+        ormsgpack.OPT_SERIALIZE_COLLECTIONS,
+        ormsgpack.OPT_SERIALIZE_DECIMAL,
+        ormsgpack.OPT_SERIALIZE_ITERABLES,
         ormsgpack.OPT_SERIALIZE_NUMPY,
         ormsgpack.OPT_SERIALIZE_PYDANTIC,
+        ormsgpack.OPT_SERIALIZE_SETS,
+        ormsgpack.OPT_SERIALIZE_TIMEDELTA,
         ormsgpack.OPT_SORT_KEYS,
+        ormsgpack.OPT_SORT_SETS,
+        ormsgpack.OPT_STRUCT_AS_ARRAY,
+        ormsgpack.OPT_TIMEDELTA_AS_MICROSECONDS,
+        ormsgpack.OPT_TIMEDELTA_AS_SECONDS,
         ormsgpack.OPT_UTC_Z,
         True,
         -1,
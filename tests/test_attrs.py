@@ -0,0 +1,126 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+from typing import Optional
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+attrs = pytest.importorskip("attrs")
+
+
+def test_attrs() -> None:
+    @attrs.define(slots=False)
+    class Point:
+        x: int
+        y: str = attrs.field(default="y")
+
+    obj = Point(1)
+    assert ormsgpack.packb(obj) == msgpack.packb({"x": 1, "y": "y"})
+
+
+def test_attrs_with_slots() -> None:
+    @attrs.define
+    class Point:
+        x: int
+        y: int
+
+    obj = Point(1, 2)
+    assert not hasattr(obj, "__dict__")
+    assert ormsgpack.packb(obj) == msgpack.packb({"x": 1, "y": 2})
+
+
+def test_attrs_frozen() -> None:
+    @attrs.frozen
+    class Point:
+        x: int
+        y: list[int]
+
+    obj = Point(1, [2])
+    assert ormsgpack.packb(obj) == msgpack.packb({"x": 1, "y": [2]})
+
+
+def test_attrs_untyped() -> None:
+    @attrs.define
+    class Point:
+        x = attrs.field()
+        y = attrs.field(default=None)
+
+    obj = Point("x")
+    assert ormsgpack.packb(obj) == msgpack.packb({"x": "x", "y": None})
+
+
+def test_attrs_subclass() -> None:
+    @attrs.define
+    class Base:
+        a: str
+
+    @attrs.define
+    class Point(Base):
+        b: int
+
+    obj = Point("a", 1)
+    assert ormsgpack.packb(obj) == msgpack.packb({"a": "a", "b": 1})
+
+
+def test_attrs_empty() -> None:
+    @attrs.define
+    class Empty:
+        pass
+
+    assert ormsgpack.packb(Empty()) == msgpack.packb({})
+
+
+@pytest.mark.parametrize("slots", (True, False))
+def test_attrs_with_private_field(slots: bool) -> None:
+    @attrs.define(slots=slots)
+    class Point:
+        x: int
+        _y: int
+
+    obj = Point(1, 2)
+    assert ormsgpack.packb(obj) == msgpack.packb({"x": 1})
+
+
+def test_attrs_nested() -> None:
+    @attrs.define
+    class Node:
+        value: int
+        child: Optional["Node"] = None
+
+    obj = Node(1, Node(2))
+    assert ormsgpack.packb(obj) == msgpack.packb(
+        {"value": 1, "child": {"value": 2, "child": None}}
+    )
+
+
+def test_attrs_circular() -> None:
+    @attrs.define
+    class Node:
+        child: Optional["Node"] = None
+
+    obj = Node()
+    obj.child = obj
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(obj)
+
+
+def test_attrs_passthrough() -> None:
+    @attrs.define
+    class Point:
+        x: int
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(Point(1), option=ormsgpack.OPT_PASSTHROUGH_ATTRS)
+
+
+def test_attrs_passthrough_default() -> None:
+    @attrs.define
+    class Point:
+        x: int
+
+    assert ormsgpack.packb(
+        Point(1),
+        option=ormsgpack.OPT_PASSTHROUGH_ATTRS,
+        default=lambda obj: [obj.x],
+    ) == msgpack.packb([1])
@@ -22,3 +22,66 @@ def test_bytes(value: bytes) -> None:
     with pytest.raises(ormsgpack.MsgpackDecodeError):
         ormsgpack.unpackb(packed)
     assert ormsgpack.unpackb(packed, option=ormsgpack.OPT_NON_STR_KEYS) == obj
+
+
+@pytest.mark.parametrize("value", BIN_PARAMS)
+@pytest.mark.parametrize("buffer_type", (bytes, bytearray, memoryview))
+def test_bytes_as_memoryview(value: bytes, buffer_type: type) -> None:
+    packed = buffer_type(ormsgpack.packb([value]))
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_BIN_AS_MEMORYVIEW)
+    view = unpacked[0]
+    assert isinstance(view, memoryview)
+    assert view.readonly
+    assert view == value
+    assert view.obj is (packed.obj if buffer_type is memoryview else packed)
+
+
+def test_bytes_as_memoryview_keeps_object_alive() -> None:
+    view = ormsgpack.unpackb(
+        ormsgpack.packb(b"abc"),
+        option=ormsgpack.OPT_BIN_AS_MEMORYVIEW,
+    )
+    assert view.tobytes() == b"abc"
+
+
+def test_bytes_as_memoryview_non_byte_format() -> None:
+    packed = memoryview(b"\xc4\x02\x01\x02").cast("H")
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_BIN_AS_MEMORYVIEW)
+    assert unpacked.format == "B"
+    assert unpacked == b"\x01\x02"
+
+
+def test_bytes_as_memoryview_key() -> None:
+    packed = ormsgpack.packb({b"a": True}, option=ormsgpack.OPT_NON_STR_KEYS)
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_BIN_AS_MEMORYVIEW | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    assert unpacked == {b"a": True}
+    assert type(next(iter(unpacked))) is bytes
+
+
+@pytest.mark.parametrize("value", BIN_PARAMS)
+def test_bytes_as_bytearray(value: bytes) -> None:
+    packed = ormsgpack.packb([value])
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_BIN_AS_BYTEARRAY)
+    assert type(unpacked[0]) is bytearray
+    assert unpacked == [value]
+
+
+def test_bytes_as_bytearray_key() -> None:
+    packed = ormsgpack.packb({b"a": b"b"}, option=ormsgpack.OPT_NON_STR_KEYS)
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_BIN_AS_BYTEARRAY | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    assert unpacked == {b"a": bytearray(b"b")}
+    assert type(next(iter(unpacked))) is bytes
+
+
+def test_bytes_as_bytearray_and_memoryview() -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            ormsgpack.packb(b"a"),
+            option=ormsgpack.OPT_BIN_AS_BYTEARRAY | ormsgpack.OPT_BIN_AS_MEMORYVIEW,
+        )
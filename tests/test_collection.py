@@ -0,0 +1,133 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import types
+from collections.abc import Iterator, Mapping, Sequence
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+
+class CustomMapping(Mapping[object, object]):
+    def __init__(self, data: dict[object, object]) -> None:
+        self.data = data
+
+    def __getitem__(self, key: object) -> object:
+        return self.data[key]
+
+    def __iter__(self) -> Iterator[object]:
+        return iter(self.data)
+
+    def __len__(self) -> int:
+        return len(self.data)
+
+
+class CustomSequence(Sequence[int]):
+    def __init__(self, length: int) -> None:
+        self.length = length
+
+    def __getitem__(self, index: int) -> int:  # type: ignore[override]
+        if index >= self.length:
+            raise IndexError
+        return index * 10
+
+    def __len__(self) -> int:
+        return self.length
+
+
+class RegisteredMapping:
+    def items(self) -> list[tuple[str, int]]:
+        return [("a", 1)]
+
+
+Mapping.register(RegisteredMapping)
+
+
+class BrokenMapping(CustomMapping):
+    def __getitem__(self, key: object) -> object:
+        raise KeyError(key)
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        types.MappingProxyType({"a": 1}),
+        collections.UserDict({"a": 1}),
+        collections.UserList([1]),
+        CustomMapping({"a": 1}),
+        CustomSequence(2),
+    ),
+)
+def test_collection_not_serialized(value: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value)
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        pytest.param(types.MappingProxyType({"a": 1}), {"a": 1}, id="mappingproxy"),
+        pytest.param(
+            collections.ChainMap({"a": 1}, {"a": 2, "b": 3}),
+            {"a": 1, "b": 3},
+            id="chainmap",
+        ),
+        pytest.param(collections.UserDict({"a": [1]}), {"a": [1]}, id="userdict"),
+        pytest.param(CustomMapping({"a": None}), {"a": None}, id="custom mapping"),
+        pytest.param(RegisteredMapping(), {"a": 1}, id="registered mapping"),
+        pytest.param(CustomMapping({}), {}, id="empty mapping"),
+    ),
+)
+def test_mapping(value: object, expected: dict[str, object]) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_COLLECTIONS)
+    assert packed == msgpack.packb(expected)
+
+
+def test_mapping_non_str_keys() -> None:
+    value = CustomMapping({1: "a", (2, 3): "b"})
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_COLLECTIONS)
+    packed = ormsgpack.packb(
+        value,
+        option=ormsgpack.OPT_SERIALIZE_COLLECTIONS | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    assert packed == msgpack.packb({1: "a", (2, 3): "b"})
+
+
+def test_mapping_sort_keys() -> None:
+    value = types.MappingProxyType({"b": 1, "a": 2, "c": 3})
+    packed = ormsgpack.packb(
+        value,
+        option=ormsgpack.OPT_SERIALIZE_COLLECTIONS | ormsgpack.OPT_SORT_KEYS,
+    )
+    assert packed == msgpack.packb({"a": 2, "b": 1, "c": 3})
+
+
+def test_mapping_exception() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            BrokenMapping({"a": 1}), option=ormsgpack.OPT_SERIALIZE_COLLECTIONS
+        )
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        pytest.param(collections.UserList([1, "a"]), [1, "a"], id="userlist"),
+        pytest.param(CustomSequence(3), [0, 10, 20], id="custom sequence"),
+        pytest.param(CustomSequence(0), [], id="empty sequence"),
+        pytest.param(range(3), [0, 1, 2], id="range"),
+        pytest.param(collections.deque([1, 2]), [1, 2], id="deque"),
+    ),
+)
+def test_sequence(value: object, expected: list[object]) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_COLLECTIONS)
+    assert packed == msgpack.packb(expected)
+
+
+def test_collection_nested() -> None:
+    value = CustomMapping({"a": collections.UserList([types.MappingProxyType({})])})
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_COLLECTIONS)
+    assert packed == msgpack.packb({"a": [{}]})
@@ -252,3 +252,178 @@ def test_dataclass_passthrough_default() -> None:
             "b": 1,
         }
     )
+
+
+def test_dataclass_metadata_name() -> None:
+    @dataclass
+    class Dataclass:
+        a: str = field(metadata={"msgpack": {"name": "id"}})
+        b: int = field(metadata={"other": 1})
+        _c: int = field(default=0, metadata={"msgpack": {"name": "c"}})
+
+    obj = Dataclass("a", 1, 2)
+    assert ormsgpack.packb(obj) == msgpack.packb(
+        {
+            "id": "a",
+            "b": 1,
+            "c": 2,
+        }
+    )
+
+
+def test_dataclass_metadata_skip() -> None:
+    @dataclass
+    class Dataclass:
+        a: str
+        b: int = field(metadata={"msgpack": {"skip": True}})
+        c: int = field(metadata={"msgpack": {"skip": False}})
+
+    obj = Dataclass("a", 1, 2)
+    assert ormsgpack.packb(obj) == msgpack.packb(
+        {
+            "a": "a",
+            "c": 2,
+        }
+    )
+
+
+def test_dataclass_metadata_with_slots() -> None:
+    @dataclass(slots=True)
+    class Dataclass:
+        a: str = field(metadata={"msgpack": {"name": "A"}})
+        b: int = field(metadata={"msgpack": {"skip": True}})
+
+    obj = Dataclass("a", 1)
+    assert not hasattr(obj, "__dict__")
+    assert ormsgpack.packb(obj) == msgpack.packb({"A": "a"})
+
+
+def test_dataclass_omit_none() -> None:
+    @dataclass
+    class Dataclass:
+        a: Optional[str]
+        b: Optional[int] = 1
+
+    assert ormsgpack.packb(
+        [Dataclass(None, None), Dataclass("a", None), Dataclass(None, 2)],
+        option=ormsgpack.OPT_OMIT_NONE,
+    ) == msgpack.packb([{}, {"a": "a"}, {"b": 2}])
+
+
+def test_dataclass_omit_defaults() -> None:
+    @dataclass
+    class Dataclass:
+        a: str
+        b: int = 1
+        c: Optional[str] = None
+        d: list[int] = field(default_factory=list)
+
+    assert ormsgpack.packb(
+        [Dataclass("a"), Dataclass("a", 2, "c", [1])],
+        option=ormsgpack.OPT_OMIT_DEFAULTS,
+    ) == msgpack.packb(
+        [{"a": "a", "d": []}, {"a": "a", "b": 2, "c": "c", "d": [1]}]
+    )
+
+
+def test_dataclass_omit_defaults_other_type() -> None:
+    @dataclass
+    class Dataclass:
+        a: object = 0
+        b: object = 1
+
+    assert ormsgpack.packb(
+        [Dataclass(False, True), Dataclass(0.0, 1.0), Dataclass(0, 1)],
+        option=ormsgpack.OPT_OMIT_DEFAULTS,
+    ) == msgpack.packb([{"a": False, "b": True}, {"a": 0.0, "b": 1.0}, {}])
+
+
+def test_dataclass_default_factory_not_called() -> None:
+    calls = []
+
+    def factory() -> list[int]:
+        calls.append(None)
+        return []
+
+    @dataclass
+    class Dataclass:
+        a: list[int] = field(default_factory=factory)
+
+    obj = Dataclass()
+    calls.clear()
+    for option in (None, ormsgpack.OPT_OMIT_DEFAULTS):
+        assert ormsgpack.packb([obj, obj], option=option) == msgpack.packb(
+            [{"a": []}, {"a": []}]
+        )
+    assert calls == []
+
+
+def test_dataclass_omit_defaults_invalid_comparison() -> None:
+    class Value(int):
+        def __eq__(self, other: object) -> bool:
+            raise ValueError
+
+        __hash__ = int.__hash__
+
+    @dataclass
+    class Dataclass:
+        a: object = Value(0)
+
+    obj = Dataclass(Value(1))
+    assert ormsgpack.packb(
+        obj, option=ormsgpack.OPT_OMIT_DEFAULTS
+    ) == msgpack.packb({"a": 1})
+
+
+def test_dataclass_field_not_set() -> None:
+    @dataclass
+    class Dataclass:
+        a: str
+        b: int
+
+        __slots__ = (
+            "a",
+            "b",
+        )
+
+    obj = Dataclass.__new__(Dataclass)
+    obj.a = "a"
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(obj)
+    assert str(exc_info.value) == "dataclass field is not set: b"
+
+
+def test_dataclass_modified() -> None:
+    @dataclass
+    class Dataclass:
+        a: int
+        b: int = field(metadata={"msgpack": {"skip": True}})
+
+    obj = Dataclass(1, 2)
+    assert ormsgpack.packb(obj) == msgpack.packb({"a": 1})
+    Dataclass.__dataclass_fields__ = {
+        "a": Dataclass.__dataclass_fields__["a"],
+        "b": field(),
+    }
+    Dataclass.__dataclass_fields__["b"].name = "b"
+    Dataclass.__dataclass_fields__["b"]._field_type = (
+        Dataclass.__dataclass_fields__["a"]._field_type
+    )
+    assert ormsgpack.packb(obj) == msgpack.packb({"a": 1, "b": 2})
+
+
+def test_dataclass_many_classes() -> None:
+    classes = []
+    for i in range(100):
+
+        @dataclass
+        class Dataclass:
+            a: int
+            b: str
+
+        classes.append(Dataclass)
+    objs = [cls(i, str(i)) for i, cls in enumerate(classes)]
+    for _ in range(2):
+        assert ormsgpack.packb(objs) == msgpack.packb(
+            [{"a": i, "b": str(i)} for i in range(100)]
+        )
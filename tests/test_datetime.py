@@ -1,6 +1,7 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 import datetime
+import struct
 import zoneinfo
 from collections.abc import Callable
 
@@ -487,6 +488,12 @@ def test_passthrough_datetime_default() -> None:
             datetime.datetime(2106, 2, 7, 6, 28, 16, tzinfo=datetime.timezone.utc),
             b"\xd7\xff\x00\x00\x00\x01\x00\x00\x00\x00",
         ),
+        (
+            datetime.datetime(
+                2106, 2, 7, 6, 28, 15, 999999, tzinfo=datetime.timezone.utc
+            ),
+            b"\xd7\xff\xeek\x18`\xff\xff\xff\xff",
+        ),
         (
             datetime.datetime(2514, 5, 30, 1, 53, 3, tzinfo=datetime.timezone.utc),
             b"\xd7\xff\x00\x00\x00\x03\xff\xff\xff\xff",
@@ -553,3 +560,246 @@ def test_datetime_naive_as_timestamp_ext() -> None:
         )
         == b"\xd6\xff\x00\x00\x00\x00"
     )
+
+
+def test_datetime_timestamp_ext_naive_utc() -> None:
+    value = datetime.datetime(2000, 1, 2, 3, 4, 5, 6, tzinfo=datetime.timezone.utc)
+    packed = ormsgpack.packb(
+        {value: [value]},
+        option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    naive = value.replace(tzinfo=None)
+    assert ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT
+        | ormsgpack.OPT_NON_STR_KEYS
+        | ormsgpack.OPT_NAIVE_UTC,
+    ) == {naive: [naive]}
+
+
+@pytest.mark.parametrize(
+    "tzinfo",
+    (
+        datetime.timezone.utc,
+        datetime.timezone(datetime.timedelta(hours=-3, minutes=-30)),
+        zoneinfo.ZoneInfo("Europe/Paris"),
+        zoneinfo.ZoneInfo("America/New_York"),
+        pytz.timezone("Asia/Kolkata"),
+        tz.gettz("Australia/Sydney"),
+    ),
+)
+def test_datetime_timestamp_ext_tzinfo(tzinfo: datetime.tzinfo) -> None:
+    values = [
+        datetime.datetime(2000, 1, 2, 3, 4, 5, 6, tzinfo=datetime.timezone.utc),
+        datetime.datetime(2024, 7, 1, tzinfo=datetime.timezone.utc),
+        datetime.datetime(2024, 11, 3, 5, 30, tzinfo=datetime.timezone.utc),
+        datetime.datetime(2024, 11, 3, 6, 30, tzinfo=datetime.timezone.utc),
+    ]
+    packed = ormsgpack.packb(
+        [{value: value} for value in values],
+        option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT | ormsgpack.OPT_NON_STR_KEYS,
+        tzinfo=tzinfo,
+    )
+    assert len(unpacked) == len(values)
+    for item, original in zip(unpacked, values):
+        expected = original.astimezone(tzinfo)
+        for obj in (*item.keys(), *item.values()):
+            assert obj.timestamp() == original.timestamp()
+            assert obj.utcoffset() == expected.utcoffset()
+            assert obj.replace(tzinfo=None) == expected.replace(tzinfo=None)
+            assert obj.fold == expected.fold
+
+
+def test_datetime_timestamp_ext_tzinfo_none() -> None:
+    packed = b"\xd6\xff\x00\x00\x00\x00"
+    assert ormsgpack.unpackb(
+        packed, option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT, tzinfo=None
+    ) == datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def test_datetime_timestamp_ext_tzinfo_invalid() -> None:
+    packed = b"\xd6\xff\x00\x00\x00\x00"
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            packed,
+            option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT,
+            tzinfo="UTC",  # type: ignore[arg-type]
+        )
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            packed,
+            option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT | ormsgpack.OPT_NAIVE_UTC,
+            tzinfo=datetime.timezone.utc,
+        )
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            msgpack.packb(msgpack.ExtType(-1, struct.pack(">Iq", 0, 253402300799))),
+            option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT,
+            tzinfo=datetime.timezone(datetime.timedelta(hours=12)),
+        )
+
+
+def pack_tz_ext(
+    seconds: int,
+    microseconds: int,
+    offset: int,
+    key: str = "",
+    tag: int = 3,
+) -> bytes:
+    data = struct.pack(">qIi", seconds, microseconds * 1000, offset) + key.encode()
+    return msgpack.packb(msgpack.ExtType(tag, data))
+
+
+@pytest.mark.parametrize(
+    ("value", "serialized_value"),
+    (
+        (
+            datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc),
+            pack_tz_ext(0, 0, 0),
+        ),
+        (
+            datetime.datetime(
+                2000,
+                1,
+                1,
+                12,
+                microsecond=123456,
+                tzinfo=datetime.timezone(datetime.timedelta(hours=5, minutes=30)),
+            ),
+            pack_tz_ext(946708200, 123456, 19800),
+        ),
+        (
+            datetime.datetime(
+                1900,
+                1,
+                1,
+                tzinfo=datetime.timezone(-datetime.timedelta(hours=8)),
+            ),
+            pack_tz_ext(-2208960000, 0, -28800),
+        ),
+        (
+            datetime.datetime(
+                2024, 7, 1, 9, tzinfo=zoneinfo.ZoneInfo("America/New_York")
+            ),
+            pack_tz_ext(1719838800, 0, -14400, "America/New_York"),
+        ),
+        (
+            datetime.datetime(
+                2024, 1, 1, 9, tzinfo=zoneinfo.ZoneInfo("America/New_York")
+            ),
+            pack_tz_ext(1704117600, 0, -18000, "America/New_York"),
+        ),
+        (
+            datetime.datetime(
+                2024, 11, 3, 1, 30, fold=1, tzinfo=zoneinfo.ZoneInfo("America/New_York")
+            ),
+            pack_tz_ext(1730615400, 0, -18000, "America/New_York"),
+        ),
+        (
+            datetime.datetime(
+                9999, 12, 31, 23, 59, 59, tzinfo=zoneinfo.ZoneInfo("UTC")
+            ),
+            pack_tz_ext(253402300799, 0, 0, "UTC"),
+        ),
+    ),
+)
+def test_datetime_as_tz_ext(
+    value: datetime.datetime,
+    serialized_value: bytes,
+) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_DATETIME_AS_TZ_EXT)
+    assert packed == serialized_value
+    assert (
+        ormsgpack.packb(
+            value,
+            option=ormsgpack.OPT_DATETIME_AS_TZ_EXT
+            | ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT,
+        )
+        == serialized_value
+    )
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_DATETIME_AS_TZ_EXT)
+    assert unpacked == value
+    assert unpacked.tzinfo == value.tzinfo
+    assert unpacked.utcoffset() == value.utcoffset()
+    assert unpacked.fold == value.fold
+
+    obj = {value: True}
+    packed = ormsgpack.packb(
+        obj, option=ormsgpack.OPT_NON_STR_KEYS | ormsgpack.OPT_DATETIME_AS_TZ_EXT
+    )
+    assert packed == b"\x81" + serialized_value + b"\xc3"
+    assert (
+        ormsgpack.unpackb(
+            packed,
+            option=ormsgpack.OPT_NON_STR_KEYS | ormsgpack.OPT_DATETIME_AS_TZ_EXT,
+        )
+        == obj
+    )
+
+
+def test_datetime_as_tz_ext_tag() -> None:
+    value = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+    packed = ormsgpack.packb(
+        [value], option=ormsgpack.OPT_DATETIME_AS_TZ_EXT, datetime_tag=42
+    )
+    assert packed == b"\x91" + pack_tz_ext(0, 0, 0, tag=42)
+    assert ormsgpack.unpackb(
+        packed, option=ormsgpack.OPT_DATETIME_AS_TZ_EXT, datetime_tag=42
+    ) == [value]
+
+
+def test_datetime_naive_as_tz_ext() -> None:
+    assert ormsgpack.packb(
+        datetime.datetime(1970, 1, 1),
+        option=ormsgpack.OPT_DATETIME_AS_TZ_EXT,
+    ) == msgpack.packb("1970-01-01T00:00:00")
+    assert ormsgpack.packb(
+        datetime.datetime(1970, 1, 1),
+        option=ormsgpack.OPT_DATETIME_AS_TZ_EXT | ormsgpack.OPT_NAIVE_UTC,
+    ) == pack_tz_ext(0, 0, 0)
+
+
+def test_datetime_as_tz_ext_zoneinfo_subclass() -> None:
+    class ComputedKeyZoneInfo(zoneinfo.ZoneInfo):
+        @property
+        def key(self) -> str:
+            return "".join(["Europe/", "Paris"])
+
+    value = datetime.datetime(
+        1970, 1, 1, 1, tzinfo=ComputedKeyZoneInfo("Europe/Paris")
+    )
+    for _ in range(100):
+        assert ormsgpack.packb(
+            value, option=ormsgpack.OPT_DATETIME_AS_TZ_EXT
+        ) == pack_tz_ext(0, 0, 3600, "Europe/Paris")
+
+
+def test_datetime_tz_ext_unknown_key() -> None:
+    assert ormsgpack.unpackb(
+        pack_tz_ext(0, 0, 3600, "Invalid/Zone"),
+        option=ormsgpack.OPT_DATETIME_AS_TZ_EXT,
+    ) == datetime.datetime(
+        1970, 1, 1, 1, tzinfo=datetime.timezone(datetime.timedelta(hours=1))
+    )
+
+
+@pytest.mark.parametrize(
+    "data",
+    (
+        pytest.param(b"\x00" * 12, id="invalid length"),
+        pytest.param(struct.pack(">qIi", 0, 1_000_000_000, 0), id="nanoseconds"),
+        pytest.param(struct.pack(">qIi", 0, 0, 86400), id="offset"),
+        pytest.param(struct.pack(">qIi", 2**62, 0, 0), id="seconds"),
+        pytest.param(struct.pack(">qIi", 0, 0, 0) + b"\xff", id="key"),
+    ),
+)
+def test_datetime_tz_ext_invalid(data: bytes) -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            msgpack.packb(msgpack.ExtType(3, data)),
+            option=ormsgpack.OPT_DATETIME_AS_TZ_EXT,
+        )
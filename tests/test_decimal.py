@@ -0,0 +1,134 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import struct
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+DECIMAL_PARAMS = (
+    pytest.param(decimal.Decimal("0"), 0, 0, id="zero"),
+    pytest.param(decimal.Decimal("1.23"), 2, 123, id="positive"),
+    pytest.param(decimal.Decimal("-1.23"), 2, -123, id="negative"),
+    pytest.param(decimal.Decimal("1.2300"), 4, 12300, id="trailing zeros"),
+    pytest.param(decimal.Decimal("1.23E+5"), -3, 123, id="positive exponent"),
+    pytest.param(
+        decimal.Decimal("123456789012345678901234567890.123456789"),
+        9,
+        123456789012345678901234567890123456789,
+        id="big",
+    ),
+)
+
+
+def test_decimal_not_serialized() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(decimal.Decimal("1.23"))
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        decimal.Decimal("1.23"),
+        decimal.Decimal("-1.23E+5"),
+        decimal.Decimal("NaN"),
+        decimal.Decimal("-Infinity"),
+    ),
+)
+def test_decimal_str(value: decimal.Decimal) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_DECIMAL)
+    assert packed == msgpack.packb(str(value))
+
+
+def test_decimal_subclass() -> None:
+    class ADecimal(decimal.Decimal):
+        pass
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(ADecimal("1.23"), option=ormsgpack.OPT_SERIALIZE_DECIMAL)
+
+
+@pytest.mark.parametrize("value, scale, unscaled", DECIMAL_PARAMS)
+def test_decimal_ext(value: decimal.Decimal, scale: int, unscaled: int) -> None:
+    data = struct.pack(">i", scale) + unscaled.to_bytes(
+        unscaled.bit_length() // 8 + 1,
+        "big",
+        signed=True,
+    )
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    assert packed == msgpack.packb(msgpack.ExtType(1, data))
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    assert isinstance(unpacked, decimal.Decimal)
+    assert str(unpacked) == str(value)
+
+    packed = ormsgpack.packb(
+        [value],
+        option=ormsgpack.OPT_DECIMAL_AS_EXT | ormsgpack.OPT_SERIALIZE_DECIMAL,
+        decimal_tag=42,
+    )
+    assert packed == msgpack.packb([msgpack.ExtType(42, data)])
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_DECIMAL_AS_EXT,
+        decimal_tag=42,
+    )
+    assert str(unpacked[0]) == str(value)
+
+    packed = msgpack.packb({msgpack.ExtType(1, data): True})
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_DECIMAL_AS_EXT | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    assert unpacked == {value: True}
+
+
+def test_decimal_ext_precision() -> None:
+    value = decimal.Decimal("1." + "1" * 100)
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    with decimal.localcontext(prec=5):
+        unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    assert str(unpacked) == str(value)
+
+
+@pytest.mark.parametrize(
+    "value",
+    (decimal.Decimal("-0"), decimal.Decimal("-0.00"), decimal.Decimal("-0E+3")),
+)
+def test_decimal_ext_negative_zero(value: decimal.Decimal) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    assert packed == ormsgpack.packb(-value, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+    assert str(unpacked) == str(-value)
+    assert unpacked.is_signed() is False
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        decimal.Decimal("NaN"),
+        decimal.Decimal("sNaN"),
+        decimal.Decimal("Infinity"),
+        decimal.Decimal("1E+3000000000"),
+    ),
+)
+def test_decimal_ext_unsupported(value: decimal.Decimal) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value, option=ormsgpack.OPT_DECIMAL_AS_EXT)
+
+
+def test_decimal_ext_invalid() -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            msgpack.packb(msgpack.ExtType(1, b"\x00\x00")),
+            option=ormsgpack.OPT_DECIMAL_AS_EXT,
+        )
+
+
+@pytest.mark.parametrize("tag", (-1, 128, None))
+def test_decimal_tag_invalid(tag: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(decimal.Decimal("1"), decimal_tag=tag)
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(b"\x00", decimal_tag=tag)
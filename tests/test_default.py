@@ -109,3 +109,108 @@ def test_default_recursion_infinite() -> None:
     with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
         ormsgpack.packb(ref, default=default)
     assert str(exc_info.value) == "Recursion limit for default hook reached"
+
+
+class Base:
+    pass
+
+
+class Derived(Base):
+    pass
+
+
+def test_default_mapping() -> None:
+    """
+    packb() default mapping
+    """
+    assert ormsgpack.packb(
+        [Decimal("1.5"), Base()],
+        default={Decimal: str, Base: lambda obj: "base"},
+    ) == msgpack.packb(["1.5", "base"])
+
+
+def test_default_mapping_mro() -> None:
+    """
+    packb() default mapping lookup along the MRO
+    """
+    assert ormsgpack.packb(
+        [Derived(), Base()],
+        default={Base: lambda obj: type(obj).__name__},
+    ) == msgpack.packb(["Derived", "Base"])
+
+
+def test_default_mapping_most_derived() -> None:
+    """
+    packb() default mapping uses the most derived class
+    """
+    assert ormsgpack.packb(
+        [Derived(), Base()],
+        default={Base: lambda obj: "base", Derived: lambda obj: "derived"},
+    ) == msgpack.packb(["derived", "base"])
+
+
+def test_default_mapping_catch_all() -> None:
+    """
+    packb() default mapping with object as catch-all
+    """
+    assert ormsgpack.packb(
+        [Decimal("1.5"), Base()],
+        default={Decimal: str, object: lambda obj: "other"},
+    ) == msgpack.packb(["1.5", "other"])
+
+
+def test_default_mapping_none() -> None:
+    """
+    packb() default mapping with None value
+    """
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Derived(), default={Derived: None, object: str})
+    assert str(exc_info.value) == "Type is not msgpack serializable: Derived"
+
+
+def test_default_mapping_missing() -> None:
+    """
+    packb() default mapping without a matching class
+    """
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Base(), default={Decimal: str})
+    assert str(exc_info.value) == "Type is not msgpack serializable: Base"
+
+
+def test_default_mapping_cache() -> None:
+    """
+    packb() default mapping lookup along the MRO once per type
+    """
+
+    class CountingMeta(type):
+        hashes = 0
+
+        def __hash__(cls) -> int:
+            CountingMeta.hashes += 1
+            return type.__hash__(cls)
+
+    class Counted(metaclass=CountingMeta):
+        pass
+
+    calls = []
+
+    def default(obj: object) -> object:
+        calls.append(obj)
+        return 0
+
+    mapping = {object: default}
+    ref = [Counted(), Counted(), Counted()]
+    CountingMeta.hashes = 0
+    assert ormsgpack.packb(ref, default=mapping) == msgpack.packb([0] * len(ref))
+    assert calls == ref
+    assert CountingMeta.hashes == 1
+
+
+def test_default_mapping_recursion() -> None:
+    """
+    packb() default mapping recursion limit
+    """
+    ref = Base()
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(ref, default={Base: lambda obj: obj})
+    assert str(exc_info.value) == "Recursion limit for default hook reached"
@@ -106,3 +106,82 @@ def test_enum_passthrough_default(value: enum.Enum) -> None:
     assert ormsgpack.packb(
         value, option=ormsgpack.OPT_PASSTHROUGH_ENUM, default=str
     ) == msgpack.packb(str(value))
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        FlagEnum.ONE,
+        FloatEnum.ONE,
+        IntEnum.ONE,
+        IntEnumEnum.ONE,
+        IntFlagEnum.ONE,
+        StrEnum.AAA,
+    ),
+)
+def test_enum_by_name(value: enum.Enum) -> None:
+    assert ormsgpack.packb(
+        value, option=ormsgpack.OPT_ENUM_BY_NAME
+    ) == msgpack.packb(value.name)
+    assert ormsgpack.packb(
+        value, option=ormsgpack.OPT_ENUM_BY_QUALIFIED_NAME
+    ) == msgpack.packb(f"{type(value).__name__}.{value.name}")
+
+
+def test_enum_by_name_dict_key() -> None:
+    obj = {IntEnum.ONE: IntEnum.ONE}
+    assert ormsgpack.packb(
+        obj, option=ormsgpack.OPT_ENUM_BY_NAME | ormsgpack.OPT_NON_STR_KEYS
+    ) == msgpack.packb({"ONE": "ONE"})
+    assert ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_ENUM_BY_QUALIFIED_NAME | ormsgpack.OPT_NON_STR_KEYS,
+    ) == msgpack.packb({"IntEnum.ONE": "IntEnum.ONE"})
+
+
+def test_enum_by_name_unserializable_value() -> None:
+    class Enum(enum.Enum):
+        A = object()
+        B = object()
+
+    assert ormsgpack.packb(
+        [Enum.A, Enum.B, Enum.A], option=ormsgpack.OPT_ENUM_BY_NAME
+    ) == msgpack.packb(["A", "B", "A"])
+
+
+def test_enum_by_name_flag_combination() -> None:
+    class Flag(enum.Flag):
+        A = 1
+        B = 2
+
+    value = Flag.A | Flag.B
+    assert ormsgpack.packb(
+        value, option=ormsgpack.OPT_ENUM_BY_NAME
+    ) == msgpack.packb(3 if value.name is None else value.name)
+    assert ormsgpack.packb(value) == msgpack.packb(3)
+
+
+def test_enum_by_name_empty_flag() -> None:
+    class Flag(enum.Flag):
+        A = 1
+        B = 2
+
+    value = Flag(0)
+    assert value.name is None
+    for option in (ormsgpack.OPT_ENUM_BY_NAME, ormsgpack.OPT_ENUM_BY_QUALIFIED_NAME):
+        assert ormsgpack.packb(value, option=option) == msgpack.packb(0)
+        assert ormsgpack.packb(
+            {value: value}, option=option | ormsgpack.OPT_NON_STR_KEYS
+        ) == msgpack.packb({0: 0})
+
+
+def test_enum_many_members() -> None:
+    Enum = enum.Enum("Enum", [f"M{i}" for i in range(100)])
+    members = list(Enum)
+    for _ in range(2):
+        assert ormsgpack.packb(members) == msgpack.packb(
+            [member.value for member in members]
+        )
+        assert ormsgpack.packb(
+            members, option=ormsgpack.OPT_ENUM_BY_NAME
+        ) == msgpack.packb([member.name for member in members])
@@ -24,3 +24,123 @@ def test_ext_type(data: bytes) -> None:
 
     with pytest.raises(ormsgpack.MsgpackEncodeError):
         ormsgpack.packb({value: True}, option=ormsgpack.OPT_NON_STR_KEYS)
+
+
+@pytest.mark.parametrize("data", EXT_PARAMS)
+def test_ext_type_as_memoryview(data: bytes) -> None:
+    packed = ormsgpack.packb(ormsgpack.Ext(1, data))
+    unpacked = ormsgpack.unpackb(
+        packed,
+        ext_hook=lambda x, y: (x, y),
+        option=ormsgpack.OPT_EXT_AS_MEMORYVIEW,
+    )
+    tag, view = unpacked
+    assert tag == 1
+    assert isinstance(view, memoryview)
+    assert view.readonly
+    assert view == data
+    assert view.obj is packed
+
+
+class Point:
+    def __init__(self, x: int, y: int) -> None:
+        self.x = x
+        self.y = y
+
+    def to_bytes(self) -> bytes:
+        return bytes([self.x, self.y])
+
+
+class Point3D(Point):
+    pass
+
+
+@pytest.mark.parametrize("tag", (-128, -2, 0, 5, 127))
+def test_ext_types(tag: int) -> None:
+    packed = ormsgpack.packb(Point(1, 2), ext_types={Point: (tag, Point.to_bytes)})
+    assert packed == msgpack.packb(msgpack.ExtType(tag, b"\x01\x02"))
+    assert ormsgpack.unpackb(packed, ext_hook=lambda x, y: (x, y)) == (
+        tag,
+        b"\x01\x02",
+    )
+
+
+def test_ext_types_nested() -> None:
+    packed = ormsgpack.packb(
+        {"a": [Point(1, 2), Point3D(3, 4)]},
+        ext_types={Point: (5, Point.to_bytes)},
+    )
+    assert packed == msgpack.packb(
+        {"a": [msgpack.ExtType(5, b"\x01\x02"), msgpack.ExtType(5, b"\x03\x04")]}
+    )
+
+
+def test_ext_types_most_derived() -> None:
+    packed = ormsgpack.packb(
+        [Point(1, 2), Point3D(3, 4)],
+        ext_types={Point: (5, Point.to_bytes), Point3D: (6, Point.to_bytes)},
+    )
+    assert packed == msgpack.packb(
+        [msgpack.ExtType(5, b"\x01\x02"), msgpack.ExtType(6, b"\x03\x04")]
+    )
+
+
+def test_ext_types_precedence() -> None:
+    packed = ormsgpack.packb(
+        Point(1, 2),
+        default=lambda obj: None,
+        ext_types={Point: (5, Point.to_bytes)},
+    )
+    assert packed == msgpack.packb(msgpack.ExtType(5, b"\x01\x02"))
+
+
+def test_ext_types_unregistered() -> None:
+    assert ormsgpack.packb(
+        Point(1, 2),
+        default=lambda obj: [obj.x, obj.y],
+        ext_types={Point3D: (5, Point.to_bytes)},
+    ) == msgpack.packb([1, 2])
+
+
+def test_ext_types_none() -> None:
+    assert ormsgpack.packb(Point(1, 2), default=lambda obj: 0, ext_types=None) == (
+        msgpack.packb(0)
+    )
+
+
+@pytest.mark.parametrize(
+    "ext_types,message",
+    (
+        ([], "Invalid ext_types"),
+        ({1: (5, Point.to_bytes)}, "ext_types keys must be classes"),
+        ({Point: 5}, "ext_types values must be (tag, encoder) tuples"),
+        ({Point: (5,)}, "ext_types values must be (tag, encoder) tuples"),
+        ({Point: (5, 6)}, "ext_types encoder must be callable"),
+    ),
+)
+def test_ext_types_invalid(ext_types: object, message: str) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(1, ext_types=ext_types)  # type: ignore[arg-type]
+    assert str(exc_info.value) == message
+
+
+@pytest.mark.parametrize("tag", (-129, 128, 2**64, "5"))
+def test_ext_types_invalid_tag(tag: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(1, ext_types={Point: (tag, Point.to_bytes)})
+    assert str(exc_info.value) == "Extension type out of range"
+
+
+def test_ext_types_encoder_raises_exception() -> None:
+    def encoder(obj: object) -> bytes:
+        raise ValueError
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Point(1, 2), ext_types={Point: (5, encoder)})
+    assert str(exc_info.value) == "ext_types encoder failed: Point"
+
+
+def test_ext_types_encoder_returns_invalid_type() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Point(1, 2), ext_types={Point: (5, lambda obj: "a")})
+    assert str(exc_info.value) == "ext_types encoder must return bytes"
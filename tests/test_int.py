@@ -70,3 +70,65 @@ def test_int_128_passthrough(value: int) -> None:
     )
     assert list(result.keys()) == ["int"]
     assert int.from_bytes(result["int"], "little", signed=True) == value
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        -9223372036854775809,
+        18446744073709551616,
+        -(2**127),
+        2**127 - 1,
+        2**1000,
+    ),
+)
+def test_int_big_int_as_ext(value: int) -> None:
+    data = value.to_bytes((value.bit_length() + 8) // 8, "big", signed=True)
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_BIG_INT_AS_EXT)
+    assert packed == msgpack.packb(msgpack.ExtType(0, data))
+    assert ormsgpack.unpackb(packed, option=ormsgpack.OPT_BIG_INT_AS_EXT) == value
+
+    packed = ormsgpack.packb(
+        [value],
+        option=ormsgpack.OPT_BIG_INT_AS_EXT,
+        big_int_tag=42,
+    )
+    assert packed == msgpack.packb([msgpack.ExtType(42, data)])
+    assert ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_BIG_INT_AS_EXT,
+        big_int_tag=42,
+    ) == [value]
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(packed, option=ormsgpack.OPT_BIG_INT_AS_EXT)
+
+    packed = msgpack.packb({msgpack.ExtType(0, data): True})
+    assert ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_BIG_INT_AS_EXT | ormsgpack.OPT_NON_STR_KEYS,
+    ) == {value: True}
+
+
+@pytest.mark.parametrize("value", INT_PARAMS)
+def test_int_64_big_int_as_ext(value: int) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_BIG_INT_AS_EXT)
+    assert packed == msgpack.packb(value)
+
+
+def test_int_big_int_as_ext_passthrough() -> None:
+    assert (
+        ormsgpack.packb(
+            2**64,
+            option=ormsgpack.OPT_BIG_INT_AS_EXT | ormsgpack.OPT_PASSTHROUGH_BIG_INT,
+            default=str,
+        )
+        == msgpack.packb(str(2**64))
+    )
+
+
+@pytest.mark.parametrize("tag", (-1, 128, None, "0"))
+def test_int_big_int_tag_invalid(tag: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(2**64, big_int_tag=tag)
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(b"\x00", big_int_tag=tag)
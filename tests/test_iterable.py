@@ -0,0 +1,131 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+from collections.abc import Iterator
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+
+class Countdown:
+    def __init__(self, start: int) -> None:
+        self.start = start
+
+    def __iter__(self) -> Iterator[int]:
+        return iter(range(self.start, 0, -1))
+
+
+class WrongLength:
+    def __init__(self, length: int) -> None:
+        self.length = length
+
+    def __len__(self) -> int:
+        return self.length
+
+    def __iter__(self) -> Iterator[int]:
+        return iter(range(2))
+
+
+def failing_generator() -> Iterator[int]:
+    yield 1
+    raise ValueError("failed")
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        range(3),
+        {"a": 1}.values(),
+        collections.deque([1, 2]),
+        (i for i in range(3)),
+        iter([1, 2]),
+        Countdown(3),
+    ),
+)
+def test_iterable_not_serialized(value: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value)
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        pytest.param(range(3), [0, 1, 2], id="range"),
+        pytest.param(range(0), [], id="empty range"),
+        pytest.param({"a": 1, "b": [2]}.values(), [1, [2]], id="dict values"),
+        pytest.param({"a": 1, "b": 2}.keys(), ["a", "b"], id="dict keys"),
+        pytest.param({"a": 1}.items(), [["a", 1]], id="dict items"),
+        pytest.param(collections.deque(["a", None]), ["a", None], id="deque"),
+        pytest.param((i * 2 for i in range(3)), [0, 2, 4], id="generator"),
+        pytest.param((i for i in ()), [], id="empty generator"),
+        pytest.param(iter([1, 2]), [1, 2], id="iterator"),
+        pytest.param(Countdown(3), [3, 2, 1], id="custom"),
+        pytest.param(map(str, range(20)), [str(i) for i in range(20)], id="map"),
+    ),
+)
+def test_iterable(value: object, expected: list[object]) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_ITERABLES)
+    assert packed == msgpack.packb(expected)
+
+
+def test_iterable_nested() -> None:
+    value = {"a": (range(i) for i in range(3))}
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_ITERABLES)
+    assert packed == msgpack.packb({"a": [[], [0], [0, 1]]})
+
+
+def test_iterable_large() -> None:
+    packed = ormsgpack.packb(
+        (i for i in range(70000)), option=ormsgpack.OPT_SERIALIZE_ITERABLES
+    )
+    assert packed == msgpack.packb(list(range(70000)))
+
+
+def test_iterable_default() -> None:
+    packed = ormsgpack.packb(
+        (object() for _ in range(2)),
+        default=lambda _: None,
+        option=ormsgpack.OPT_SERIALIZE_ITERABLES,
+    )
+    assert packed == msgpack.packb([None, None])
+
+
+def test_iterable_invalid_element() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            (object() for _ in range(2)), option=ormsgpack.OPT_SERIALIZE_ITERABLES
+        )
+
+
+def test_iterable_exception() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(failing_generator(), option=ormsgpack.OPT_SERIALIZE_ITERABLES)
+
+
+@pytest.mark.parametrize("length", (1, 3))
+def test_iterable_wrong_length(length: int) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(WrongLength(length), option=ormsgpack.OPT_SERIALIZE_ITERABLES)
+
+
+def test_iterable_str_subclass_not_iterated() -> None:
+    class Str(str):
+        pass
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            Str("abc"),
+            option=ormsgpack.OPT_SERIALIZE_ITERABLES
+            | ormsgpack.OPT_PASSTHROUGH_SUBCLASS,
+        )
+
+
+def test_iterable_recursion() -> None:
+    def nested(depth: int) -> Iterator[object]:
+        if depth:
+            yield nested(depth - 1)
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(nested(1024), option=ormsgpack.OPT_SERIALIZE_ITERABLES)
@@ -0,0 +1,123 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+
+class Point:
+    def __init__(self, x: int, y: int) -> None:
+        self.x = x
+        self.y = y
+
+    def __msgpack__(self) -> object:
+        return [self.x, self.y]
+
+
+class Point3D(Point):
+    def __init__(self, x: int, y: int, z: int) -> None:
+        super().__init__(x, y)
+        self.z = z
+
+    def __msgpack__(self) -> object:
+        return {"point": super().__msgpack__(), "z": self.z}
+
+
+class InheritedPoint(Point):
+    pass
+
+
+class Wrapper:
+    def __init__(self, value: object) -> None:
+        self.value = value
+
+    def __msgpack__(self) -> object:
+        return self.value
+
+
+class Recursive:
+    def __msgpack__(self) -> object:
+        return self
+
+
+class Failing:
+    def __msgpack__(self) -> object:
+        raise ValueError
+
+
+class Disabled(Point):
+    __msgpack__ = None  # type: ignore[assignment]
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+
+    def __msgpack__(self) -> object:
+        return self.a
+
+
+def test_msgpack_method() -> None:
+    assert ormsgpack.packb(Point(1, 2)) == msgpack.packb([1, 2])
+
+
+def test_msgpack_method_nested() -> None:
+    obj = {"a": [Point(1, 2), Point3D(3, 4, 5)]}
+    assert ormsgpack.packb(obj) == msgpack.packb(
+        {"a": [[1, 2], {"point": [3, 4], "z": 5}]}
+    )
+
+
+def test_msgpack_method_inherited() -> None:
+    assert ormsgpack.packb(InheritedPoint(1, 2)) == msgpack.packb([1, 2])
+
+
+def test_msgpack_method_disabled() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(Disabled(1, 2))
+    packed = ormsgpack.packb(Disabled(1, 2), default=lambda obj: obj.x)
+    assert packed == msgpack.packb(1)
+
+
+def test_msgpack_method_precedence() -> None:
+    assert ormsgpack.packb(Dataclass(1)) == msgpack.packb(1)
+    packed = ormsgpack.packb(Point(1, 2), default=lambda obj: "default")
+    assert packed == msgpack.packb([1, 2])
+
+
+def test_msgpack_method_returns_default_type() -> None:
+    packed = ormsgpack.packb(Wrapper(object()), default=lambda obj: "default")
+    assert packed == msgpack.packb("default")
+
+
+def test_msgpack_method_returns_ext() -> None:
+    packed = ormsgpack.packb(Wrapper(ormsgpack.Ext(1, b"a")))
+    assert packed == msgpack.packb(msgpack.ExtType(1, b"a"))
+
+
+def test_msgpack_method_returns_fragment() -> None:
+    fragment = ormsgpack.Fragment(msgpack.packb({"a": 1}))
+    packed = ormsgpack.packb(Wrapper(fragment))
+    assert packed == msgpack.packb({"a": 1})
+
+
+def test_msgpack_method_recursion() -> None:
+    value: object = 0
+    for _ in range(254):
+        value = Wrapper(value)
+    assert ormsgpack.packb(value) == msgpack.packb(0)
+
+
+def test_msgpack_method_recursion_infinite() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Recursive())
+    assert str(exc_info.value) == "Recursion limit for default hook reached"
+
+
+def test_msgpack_method_raises_exception() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(Failing())
+    assert str(exc_info.value) == "__msgpack__ method failed: Failing"
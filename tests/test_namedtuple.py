@@ -0,0 +1,114 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import dataclasses
+from typing import NamedTuple
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+
+class Point(NamedTuple):
+    x: int
+    y: int = 0
+
+
+class Point3D(Point):
+    pass
+
+
+Pair = collections.namedtuple("Pair", ["first", "second"])
+
+
+class FakeFields(tuple[int, ...]):
+    _fields = ("a", 1)
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+    b: str
+
+
+def test_namedtuple_not_serialized() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(Point(1, 2))
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        (Point(1, 2), {"x": 1, "y": 2}),
+        (Point3D(1), {"x": 1, "y": 0}),
+        (Pair("a", [None]), {"first": "a", "second": [None]}),
+        (
+            [Pair(1, 2), Pair(3, 4)],
+            [{"first": 1, "second": 2}, {"first": 3, "second": 4}],
+        ),
+    ),
+)
+def test_namedtuple_as_map(value: object, expected: object) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_NAMEDTUPLE_AS_MAP)
+    assert packed == msgpack.packb(expected)
+
+
+def test_namedtuple_as_map_nested() -> None:
+    value = Pair(Point(1, 2), (3, 4))
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_NAMEDTUPLE_AS_MAP)
+    assert packed == msgpack.packb({"first": {"x": 1, "y": 2}, "second": [3, 4]})
+
+
+def test_namedtuple_as_map_not_namedtuple() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(FakeFields((1, 2)), option=ormsgpack.OPT_NAMEDTUPLE_AS_MAP)
+
+
+def test_namedtuple_as_map_many_types() -> None:
+    for i in range(200):
+        cls = collections.namedtuple(f"T{i}", ["a", f"b{i}"])  # type: ignore[misc]
+        packed = ormsgpack.packb(cls(i, 1), option=ormsgpack.OPT_NAMEDTUPLE_AS_MAP)
+        assert packed == msgpack.packb({"a": i, f"b{i}": 1})
+
+
+def test_unpackb_type_namedtuple() -> None:
+    value = Point(1, 2)
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_NAMEDTUPLE_AS_MAP)
+    unpacked = ormsgpack.unpackb(packed, type=Point)
+    assert isinstance(unpacked, Point)
+    assert unpacked == value
+
+
+def test_unpackb_type_namedtuple_array() -> None:
+    packed = ormsgpack.packb([1, 2])
+    unpacked = ormsgpack.unpackb(packed, type=Point)
+    assert isinstance(unpacked, Point)
+    assert unpacked == Point(1, 2)
+
+
+def test_unpackb_type_dataclass() -> None:
+    packed = ormsgpack.packb({"a": 1, "b": "b"})
+    assert ormsgpack.unpackb(packed, type=Dataclass) == Dataclass(1, "b")
+
+
+def test_unpackb_type_none() -> None:
+    packed = ormsgpack.packb([1, 2])
+    assert ormsgpack.unpackb(packed, type=None) == [1, 2]
+
+
+@pytest.mark.parametrize("value", (1, "a", None))
+def test_unpackb_type_invalid_object(value: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(ormsgpack.packb(value), type=Point)
+
+
+@pytest.mark.parametrize("value", ([1, 2, 3], {"z": 1}, {}))
+def test_unpackb_type_invalid_arguments(value: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(ormsgpack.packb(value), type=Point)
+
+
+def test_unpackb_type_invalid() -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(ormsgpack.packb([]), type=1)  # type: ignore[arg-type]
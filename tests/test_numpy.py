@@ -1,5 +1,8 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+import subprocess
+import sys
+
 import msgpack
 import pytest
 
@@ -21,6 +24,10 @@ numpy = pytest.importorskip("numpy")
         numpy.uint32,
         numpy.uint64,
         numpy.uintp,
+        numpy.intc,
+        numpy.uintc,
+        numpy.longlong,
+        numpy.ulonglong,
     ),
 )
 def test_numpy_array_d1_integer(dtype: type) -> None:
@@ -266,30 +273,69 @@ def test_numpy_array_d3_f64() -> None:
 
 
 def test_numpy_array_fortran() -> None:
-    array = numpy.array([[1, 2], [3, 4]], order="F")
+    array = numpy.array([[1, 2, 3], [4, 5, 6]], order="F")
     assert array.flags["F_CONTIGUOUS"] is True
-    with pytest.raises(ormsgpack.MsgpackEncodeError):
-        ormsgpack.packb(array, option=ormsgpack.OPT_SERIALIZE_NUMPY)
     assert ormsgpack.packb(
-        array, default=lambda x: x.tolist(), option=ormsgpack.OPT_SERIALIZE_NUMPY
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
     ) == ormsgpack.packb(array.tolist())
 
 
-def test_numpy_array_non_contiguous_message() -> None:
-    array = numpy.array([[1, 2], [3, 4]], order="F")
-    assert array.flags["F_CONTIGUOUS"] is True
-    try:
+@pytest.mark.parametrize(
+    "array",
+    (
+        numpy.arange(24, dtype=numpy.int32).reshape(4, 6)[:, ::2],
+        numpy.arange(24, dtype=numpy.int64).reshape(4, 6)[::-1, 1:4],
+        numpy.arange(24, dtype=numpy.uint16).reshape(2, 3, 4).transpose(2, 0, 1),
+        numpy.arange(24, dtype=numpy.float64)[::-3],
+        numpy.arange(24, dtype=numpy.float16).reshape(4, 6).T,
+        numpy.arange(24, dtype=numpy.int8).reshape(4, 6)[1::2, ::-2],
+        (numpy.arange(24) % 3 == 0).reshape(4, 6)[:, 1::2],
+    ),
+)
+def test_numpy_array_non_contiguous(array: numpy.ndarray) -> None:
+    assert array.flags["C_CONTIGUOUS"] is False
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == ormsgpack.packb(
+        numpy.ascontiguousarray(array), option=ormsgpack.OPT_SERIALIZE_NUMPY
+    )
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == ormsgpack.packb(array.tolist())
+
+
+def test_numpy_array_empty_slice() -> None:
+    array = numpy.arange(24, dtype=numpy.int32).reshape(4, 6)[:0, ::2]
+    assert array.shape == (0, 3)
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == ormsgpack.packb([])
+
+
+def test_numpy_array_non_contiguous_datetime64() -> None:
+    array = numpy.array(
+        [
+            [numpy.datetime64("2021-01-01T00:00:00"), numpy.datetime64("2021-01-02")],
+            [numpy.datetime64("2021-01-03"), numpy.datetime64("2021-01-04")],
+        ]
+    ).T
+    assert array.flags["C_CONTIGUOUS"] is False
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == ormsgpack.packb(
+        numpy.ascontiguousarray(array), option=ormsgpack.OPT_SERIALIZE_NUMPY
+    )
+
+
+def test_numpy_array_unsupported_dtype_message() -> None:
+    array = numpy.array([[1, 2], [3, 4]], object)
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
         ormsgpack.packb(array, option=ormsgpack.OPT_SERIALIZE_NUMPY)
-        assert False
-    except TypeError as exc:
-        assert (
-            str(exc)
-            == "numpy array is not C contiguous; use ndarray.tolist() in default"
-        )
+    assert str(exc_info.value) == "unsupported datatype in numpy array"
 
 
 def test_numpy_array_unsupported_dtype() -> None:
-    array = numpy.array([[1, 2], [3, 4]], numpy.csingle)
+    array = numpy.array([[1, 2], [3, 4]], object)
     with pytest.raises(ormsgpack.MsgpackEncodeError):
         ormsgpack.packb(array, option=ormsgpack.OPT_SERIALIZE_NUMPY)
 
@@ -362,8 +408,9 @@ def test_numpy_array_4_stride() -> None:
 def test_numpy_array_dimension_zero() -> None:
     array = numpy.array(0)
     assert array.ndim == 0
-    with pytest.raises(ormsgpack.MsgpackEncodeError):
-        ormsgpack.packb(array, option=ormsgpack.OPT_SERIALIZE_NUMPY)
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb(0)
 
     array = numpy.empty((0, 4, 2))
     assert (
@@ -459,6 +506,10 @@ def test_numpy_array_dimension_max() -> None:
         numpy.uint32,
         numpy.uint64,
         numpy.uintp,
+        numpy.intc,
+        numpy.uintc,
+        numpy.longlong,
+        numpy.ulonglong,
     ),
 )
 def test_numpy_scalar_integer(dtype: type) -> None:
@@ -632,3 +683,265 @@ def test_numpy_datetime64_unsupported_unit() -> None:
             numpy.datetime64("NaT"),
             option=ormsgpack.OPT_SERIALIZE_NUMPY,
         )
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        numpy.array(1.5),
+        numpy.array(True),
+        numpy.array(1 + 2j),
+        numpy.array("abc"),
+        numpy.array(b"abc"),
+        numpy.array(numpy.datetime64("2021-01-01T00:00:00")),
+    ),
+)
+def test_numpy_array_d0(value: numpy.ndarray) -> None:
+    assert value.ndim == 0
+    assert ormsgpack.packb(
+        value, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == ormsgpack.packb(value[()], option=ormsgpack.OPT_SERIALIZE_NUMPY)
+
+
+def test_numpy_array_complex() -> None:
+    array = numpy.array([[1 + 2j, -3.5j], [0, 4.25]], numpy.complex128)
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([[[1.0, 2.0], [0.0, -3.5]], [[0.0, 0.0], [4.25, 0.0]]])
+
+    array = numpy.array([1 + 2j, -3.5j], numpy.complex64)
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([[1.0, 2.0], [0.0, -3.5]], use_single_float=True)
+
+
+def test_numpy_scalar_complex() -> None:
+    assert ormsgpack.packb(
+        numpy.complex128(1 - 2j), option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([1.0, -2.0])
+    assert ormsgpack.packb(
+        numpy.complex64(1 - 2j), option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([1.0, -2.0], use_single_float=True)
+
+
+def test_numpy_array_str() -> None:
+    array = numpy.array([["a", ""], ["héllo", "\U0001f600"]])
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([["a", ""], ["héllo", "\U0001f600"]])
+
+
+def test_numpy_array_bytes() -> None:
+    array = numpy.array([b"a", b"", b"a\x00b"])
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb([b"a", b"", b"a\x00b"])
+
+
+def test_numpy_scalar_str_bytes() -> None:
+    assert ormsgpack.packb(
+        [numpy.str_("abc"), numpy.bytes_(b"abc")],
+        option=ormsgpack.OPT_SERIALIZE_NUMPY | ormsgpack.OPT_PASSTHROUGH_SUBCLASS,
+    ) == msgpack.packb(["abc", b"abc"])
+
+
+def test_numpy_longdouble() -> None:
+    assert ormsgpack.packb(
+        numpy.longdouble(1.5), option=ormsgpack.OPT_SERIALIZE_NUMPY
+    ) == msgpack.packb(1.5)
+    assert ormsgpack.packb(
+        numpy.array([[1.5, -2.25]], numpy.longdouble),
+        option=ormsgpack.OPT_SERIALIZE_NUMPY,
+    ) == msgpack.packb([[1.5, -2.25]])
+
+
+@pytest.mark.parametrize(
+    "option",
+    (
+        ormsgpack.OPT_SERIALIZE_TIMEDELTA,
+        ormsgpack.OPT_TIMEDELTA_AS_EXT,
+        ormsgpack.OPT_TIMEDELTA_AS_MICROSECONDS,
+        ormsgpack.OPT_TIMEDELTA_AS_SECONDS,
+    ),
+)
+def test_numpy_timedelta64(option: int) -> None:
+    values = [
+        numpy.timedelta64(3, "W"),
+        numpy.timedelta64(-2, "D"),
+        numpy.timedelta64(5, "h"),
+        numpy.timedelta64(1_500, "ms"),
+        numpy.timedelta64(-1_234_567, "us"),
+        numpy.timedelta64(1_234_567_891, "ns"),
+    ]
+    expected = ormsgpack.packb(
+        [value.astype("m8[us]").item() for value in values], option=option
+    )
+    assert (
+        ormsgpack.packb(values, option=ormsgpack.OPT_SERIALIZE_NUMPY | option)
+        == expected
+    )
+    array = numpy.array([1_500, -1_234_567], "m8[us]")
+    assert ormsgpack.packb(
+        array, option=ormsgpack.OPT_SERIALIZE_NUMPY | option
+    ) == ormsgpack.packb(array.tolist(), option=option)
+
+
+def test_numpy_timedelta64_disabled() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            numpy.timedelta64(1, "s"), option=ormsgpack.OPT_SERIALIZE_NUMPY
+        )
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            numpy.array([1], "m8[s]"), option=ormsgpack.OPT_SERIALIZE_NUMPY
+        )
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        numpy.timedelta64(1, "Y"),
+        numpy.timedelta64(1, "M"),
+        numpy.timedelta64("NaT"),
+        numpy.timedelta64(2**62, "W"),
+    ),
+)
+def test_numpy_timedelta64_unrepresentable(value: numpy.timedelta64) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            value,
+            option=ormsgpack.OPT_SERIALIZE_NUMPY | ormsgpack.OPT_SERIALIZE_TIMEDELTA,
+        )
+
+
+@pytest.mark.parametrize(
+    "array",
+    (
+        numpy.array([True, False]),
+        numpy.array([[1, -2], [3, -4]], numpy.int8),
+        numpy.array([[1, -2], [3, -4]], numpy.int64),
+        numpy.array([1, 2**64 - 1], numpy.uint64),
+        numpy.array([1.5, -2.25], numpy.float16),
+        numpy.array([[1.5, -2.25]], numpy.float64),
+        numpy.array([1.5 - 2.25j], numpy.complex128),
+        numpy.array(["2021-01-01T00:00:00"], "M8[ns]"),
+        numpy.array([1_500, -1_234_567], "m8[us]"),
+        numpy.array([b"a", b"bc"], "S2"),
+        numpy.array(["a", "\U0001f600"], "U3"),
+        numpy.array(1.5),
+        numpy.zeros((0, 3), numpy.int32),
+        numpy.arange(24, dtype=numpy.int32).reshape(2, 3, 4),
+    ),
+)
+def test_numpy_ext(array: numpy.ndarray) -> None:
+    packed = ormsgpack.packb(array, option=ormsgpack.OPT_NUMPY_AS_EXT)
+    ext = msgpack.unpackb(packed)
+    assert ext.code == 4
+    descr = array.dtype.str.encode()
+    assert ext.data == (
+        bytes([len(descr)])
+        + descr
+        + bytes([array.ndim])
+        + b"".join(dim.to_bytes(8, "big") for dim in array.shape)
+        + array.tobytes()
+    )
+    obj = ormsgpack.unpackb(packed, option=ormsgpack.OPT_NUMPY_AS_EXT)
+    assert obj.dtype == array.dtype
+    assert obj.shape == array.shape
+    assert obj.flags.writeable
+    numpy.testing.assert_array_equal(obj, array)
+
+
+@pytest.mark.parametrize(
+    "array",
+    (
+        numpy.arange(24, dtype=numpy.int32).reshape(2, 3, 4).transpose(),
+        numpy.arange(24, dtype=numpy.float64).reshape(4, 6)[::2, 1::3],
+        numpy.asfortranarray(numpy.arange(6, dtype=numpy.int16).reshape(2, 3)),
+        numpy.array([[1, -2], [3, -4]], ">i4"),
+        numpy.array([1.5 - 2.25j], ">c8"),
+        numpy.array(["ab", "c"], ">U2"),
+    ),
+)
+def test_numpy_ext_layout(array: numpy.ndarray) -> None:
+    expected = numpy.ascontiguousarray(array, array.dtype.newbyteorder("<"))
+    ext = msgpack.unpackb(
+        ormsgpack.packb(array, option=ormsgpack.OPT_NUMPY_AS_EXT)
+    )
+    assert ext.data.endswith(expected.tobytes())
+    obj = ormsgpack.unpackb(
+        msgpack.packb(ext), option=ormsgpack.OPT_NUMPY_AS_EXT
+    )
+    assert obj.dtype == expected.dtype
+    numpy.testing.assert_array_equal(obj, array)
+
+
+def test_numpy_ext_unsupported() -> None:
+    array = numpy.array([1, "a"], object)
+    assert ormsgpack.packb(
+        array,
+        default=lambda obj: obj.tolist(),
+        option=ormsgpack.OPT_NUMPY_AS_EXT,
+    ) == msgpack.packb([1, "a"])
+
+
+def test_numpy_ext_scalar() -> None:
+    assert ormsgpack.packb(
+        [numpy.int32(1), numpy.float64(1.5)], option=ormsgpack.OPT_NUMPY_AS_EXT
+    ) == msgpack.packb([1, 1.5])
+
+
+def test_numpy_ext_tag() -> None:
+    array = numpy.array([1, 2], numpy.int32)
+    packed = ormsgpack.packb(array, option=ormsgpack.OPT_NUMPY_AS_EXT, numpy_tag=42)
+    assert msgpack.unpackb(packed).code == 42
+    obj = ormsgpack.unpackb(
+        packed, option=ormsgpack.OPT_NUMPY_AS_EXT, numpy_tag=42
+    )
+    numpy.testing.assert_array_equal(obj, array)
+
+
+@pytest.mark.parametrize(
+    "data",
+    (
+        b"",
+        b"\x03<i4",
+        b"\x03<i4\x01\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00",
+        b"\x02|O\x01\x00\x00\x00\x00\x00\x00\x00\x01" + bytes(8),
+        b"\x04|V16\x01\x00\x00\x00\x00\x00\x00\x00\x01" + bytes(16),
+    ),
+)
+def test_numpy_ext_invalid(data: bytes) -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            msgpack.packb(msgpack.ExtType(4, data)),
+            option=ormsgpack.OPT_NUMPY_AS_EXT,
+        )
+
+
+def test_numpy_ext_numpy_missing() -> None:
+    code = """
+import sys
+
+sys.modules["numpy"] = None
+
+import ormsgpack
+
+data = b"\\xc7\\x11\\x04\\x03<i4\\x01" + bytes(7) + b"\\x01\\x01" + bytes(3)
+try:
+    ormsgpack.unpackb(data, option=ormsgpack.OPT_NUMPY_AS_EXT)
+except ormsgpack.MsgpackDecodeError as exc:
+    print(exc)
+"""
+    result = subprocess.run(
+        [sys.executable, "-c", code], capture_output=True, check=True, text=True
+    )
+    assert result.stdout == "numpy is not available\n"
+
+
+@pytest.mark.parametrize("tag", (-1, 128, None))
+def test_numpy_tag_invalid(tag: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(numpy.array([1]), numpy_tag=tag)
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(b"\x00", numpy_tag=tag)
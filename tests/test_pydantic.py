@@ -107,3 +107,139 @@ def test_pydantic_model_with_extra() -> None:
         ("b", 1),
         ("c", 2),
     ]
+
+
+class AliasModel(pydantic.BaseModel):
+    model_config = pydantic.ConfigDict(populate_by_name=True)
+
+    a: int = pydantic.Field(serialization_alias="A")
+    b: int = pydantic.Field(alias="B")
+    c: int = pydantic.Field(default=0, exclude=True)
+    d: int = 0
+
+    @pydantic.computed_field  # type: ignore[prop-decorator]
+    @property
+    def e(self) -> int:
+        return self.a + self.b
+
+    @pydantic.computed_field(alias="F")  # type: ignore[prop-decorator]
+    @property
+    def f(self) -> str:
+        return "f"
+
+
+def test_pydantic_model_fields_default() -> None:
+    obj = AliasModel(a=1, b=2)
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_SERIALIZE_PYDANTIC)
+    assert ormsgpack.unpackb(packed) == {"a": 1, "b": 2, "c": 0, "d": 0}
+
+
+def test_pydantic_model_by_alias() -> None:
+    obj = AliasModel(a=1, b=2)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_PYDANTIC_BY_ALIAS,
+    )
+    assert ormsgpack.unpackb(packed) == {"A": 1, "B": 2, "c": 0, "d": 0}
+
+
+def test_pydantic_model_exclude() -> None:
+    obj = AliasModel(a=1, b=2)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_PYDANTIC_EXCLUDE,
+    )
+    assert ormsgpack.unpackb(packed) == {"a": 1, "b": 2, "d": 0}
+
+
+@pytest.mark.skipif(
+    sys.version_info >= (3, 14),
+    reason="pydantic v1 does not support Python 3.14 and greater",
+)
+def test_pydantic_v1_model_by_alias_exclude() -> None:
+    import pydantic.v1
+
+    class Model(pydantic.v1.BaseModel):
+        a: int = pydantic.v1.Field(alias="A")
+        b: int = pydantic.v1.Field(0, exclude=True)
+
+    obj = Model(A=1)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC
+        | ormsgpack.OPT_PYDANTIC_BY_ALIAS
+        | ormsgpack.OPT_PYDANTIC_EXCLUDE,
+    )
+    assert packed == ormsgpack.packb(obj.dict(by_alias=True))
+    assert ormsgpack.unpackb(packed) == {"A": 1}
+
+
+def test_pydantic_model_computed_fields() -> None:
+    obj = AliasModel(a=1, b=2)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC
+        | ormsgpack.OPT_PYDANTIC_COMPUTED_FIELDS,
+    )
+    assert ormsgpack.unpackb(packed) == {
+        "a": 1,
+        "b": 2,
+        "c": 0,
+        "d": 0,
+        "e": 3,
+        "f": "f",
+    }
+
+
+def test_pydantic_model_dump_by_alias() -> None:
+    obj = AliasModel(a=1, b=2, d=4)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC
+        | ormsgpack.OPT_PYDANTIC_BY_ALIAS
+        | ormsgpack.OPT_PYDANTIC_COMPUTED_FIELDS
+        | ormsgpack.OPT_PYDANTIC_EXCLUDE,
+    )
+    assert packed == ormsgpack.packb(obj.model_dump(by_alias=True))
+
+
+def test_pydantic_model_fields_sort_keys() -> None:
+    obj = AliasModel(a=1, b=2)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC
+        | ormsgpack.OPT_PYDANTIC_BY_ALIAS
+        | ormsgpack.OPT_PYDANTIC_COMPUTED_FIELDS
+        | ormsgpack.OPT_SORT_KEYS,
+    )
+    assert list(ormsgpack.unpackb(packed)) == ["A", "B", "F", "c", "d", "e"]
+
+
+def test_pydantic_model_computed_field_raises_exception() -> None:
+    class Model(pydantic.BaseModel):
+        @pydantic.computed_field  # type: ignore[prop-decorator]
+        @property
+        def a(self) -> int:
+            raise ValueError
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError) as exc_info:
+        ormsgpack.packb(
+            Model(),
+            option=ormsgpack.OPT_SERIALIZE_PYDANTIC
+            | ormsgpack.OPT_PYDANTIC_COMPUTED_FIELDS,
+        )
+    assert str(exc_info.value) == "Pydantic computed field raised an exception: a"
+
+
+def test_pydantic_model_fields_with_extra() -> None:
+    class Model(pydantic.BaseModel):
+        model_config = pydantic.ConfigDict(extra="allow")
+
+        a: int = pydantic.Field(serialization_alias="A")
+
+    obj = Model(a=1, b=2)  # type: ignore[call-arg]
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_PYDANTIC_BY_ALIAS,
+    )
+    assert ormsgpack.unpackb(packed) == {"A": 1, "b": 2}
@@ -0,0 +1,108 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+
+class SetSubclass(set[object]):
+    pass
+
+
+@pytest.mark.parametrize("value", (set(), frozenset()))
+def test_set_not_serialized(value: object) -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value)
+
+
+@pytest.mark.parametrize(
+    "value",
+    (
+        {1, 2, 3},
+        frozenset({"a", "b", "c"}),
+        {(1, 2), None, 3.5, b"bytes"},
+        set(),
+        frozenset(),
+    ),
+)
+def test_set(value: set[object]) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_SETS)
+    unpacked = ormsgpack.unpackb(packed)
+    assert isinstance(unpacked, list)
+    assert len(unpacked) == len(value)
+    assert {tuple(v) if isinstance(v, list) else v for v in unpacked} == value
+
+
+def test_set_nested() -> None:
+    value = {"a": {frozenset({1}), frozenset({2})}}
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_SETS)
+    unpacked = ormsgpack.unpackb(packed)
+    assert sorted(unpacked["a"]) == [[1], [2]]
+
+
+def test_set_subclass() -> None:
+    value = SetSubclass({1})
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_SETS)
+    assert packed == msgpack.packb([1])
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            value,
+            option=ormsgpack.OPT_SERIALIZE_SETS | ormsgpack.OPT_PASSTHROUGH_SUBCLASS,
+        )
+
+
+def test_set_default() -> None:
+    packed = ormsgpack.packb(
+        {object()},
+        default=lambda _: "default",
+        option=ormsgpack.OPT_SERIALIZE_SETS,
+    )
+    assert packed == msgpack.packb(["default"])
+
+
+def test_set_invalid() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb({object()}, option=ormsgpack.OPT_SERIALIZE_SETS)
+
+
+@pytest.mark.parametrize(
+    "option",
+    (
+        ormsgpack.OPT_SORT_SETS,
+        ormsgpack.OPT_SERIALIZE_SETS | ormsgpack.OPT_SORT_SETS,
+    ),
+)
+def test_set_sorted(option: int) -> None:
+    value = {"b", "a", 3, 1, -1, 2.5, None, (2, 1), (1, 2)}
+    items = sorted(msgpack.packb(v) for v in value)
+    packed = ormsgpack.packb(value, option=option)
+    assert packed == bytes([0x90 | len(items)]) + b"".join(items)
+
+
+def test_set_sorted_deterministic() -> None:
+    values = [str(i) for i in range(100)]
+    packed = ormsgpack.packb(set(values), option=ormsgpack.OPT_SORT_SETS)
+    assert packed == ormsgpack.packb(
+        set(reversed(values)), option=ormsgpack.OPT_SORT_SETS
+    )
+    assert ormsgpack.unpackb(packed) == sorted(values, key=msgpack.packb)
+
+
+def test_set_sorted_nested() -> None:
+    value = frozenset({frozenset({3, 1}), frozenset({2})})
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SORT_SETS)
+    assert packed == msgpack.packb([[2], [1, 3]])
+
+
+def test_set_sorted_error() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb({1, object()}, option=ormsgpack.OPT_SORT_SETS)
+
+
+def test_set_sorted_recursion() -> None:
+    value: frozenset[object] = frozenset()
+    for _ in range(1024):
+        value = frozenset({value})
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(value, option=ormsgpack.OPT_SORT_SETS)
@@ -44,3 +44,70 @@ def test_str_surrogates() -> None:
 
     packed = ormsgpack.packb("\ud800", option=ormsgpack.OPT_REPLACE_SURROGATES)
     assert ormsgpack.unpackb(packed) == "?"
+
+
+INVALID_STR_PARAMS = (
+    pytest.param(b"a\xff", id="short"),
+    pytest.param(b"a" * 64 + b"\xff", id="long"),
+)
+
+
+def pack_str(data: bytes) -> bytes:
+    return b"\xd9" + bytes([len(data)]) + data
+
+
+@pytest.mark.parametrize("data", INVALID_STR_PARAMS)
+def test_str_invalid(data: bytes) -> None:
+    packed = pack_str(data)
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(packed)
+
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_INVALID_STR_AS_BYTES)
+    assert unpacked == data
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_INVALID_STR_REPLACE)
+    assert unpacked == data.decode("utf-8", "replace")
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_INVALID_STR_SURROGATEESCAPE,
+    )
+    assert unpacked == data.decode("utf-8", "surrogateescape")
+
+
+@pytest.mark.parametrize("data", INVALID_STR_PARAMS)
+@pytest.mark.parametrize(
+    "option",
+    (
+        pytest.param(None, id="str keys"),
+        pytest.param(ormsgpack.OPT_NON_STR_KEYS, id="non str keys"),
+    ),
+)
+def test_str_invalid_key(data: bytes, option: int | None) -> None:
+    packed = b"\x81" + pack_str(data) + b"\xc3"
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(packed, option=option)
+
+    options = option or 0
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=options | ormsgpack.OPT_INVALID_STR_AS_BYTES,
+    )
+    assert unpacked == {data: True}
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=options | ormsgpack.OPT_INVALID_STR_REPLACE,
+    )
+    assert unpacked == {data.decode("utf-8", "replace"): True}
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=options | ormsgpack.OPT_INVALID_STR_SURROGATEESCAPE,
+    )
+    assert unpacked == {data.decode("utf-8", "surrogateescape"): True}
+
+
+def test_str_invalid_options() -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            pack_str(b"a"),
+            option=ormsgpack.OPT_INVALID_STR_AS_BYTES
+            | ormsgpack.OPT_INVALID_STR_REPLACE,
+        )
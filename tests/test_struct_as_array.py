@@ -0,0 +1,234 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+import dataclasses
+from typing import Optional
+
+import msgpack
+import pydantic
+import pytest
+
+import ormsgpack
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: int = 0
+    label: Optional[str] = dataclasses.field(
+        default=None, metadata={"msgpack": {"name": "l"}}
+    )
+    _private: int = 0
+    cache: dict[str, int] = dataclasses.field(
+        default_factory=dict, metadata={"msgpack": {"skip": True}}
+    )
+
+
+@dataclasses.dataclass
+class Derived:
+    a: int
+    b: int = dataclasses.field(init=False, default=2)
+
+
+@dataclasses.dataclass(slots=True)
+class Slotted:
+    a: int
+    b: str
+
+
+class Model(pydantic.BaseModel):
+    a: int
+    b: str = pydantic.Field(alias="B")
+
+
+def test_dataclass() -> None:
+    obj = Point(1, 2, "p")
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([1, 2, "p"])
+    assert ormsgpack.unpackb(packed, type=Point) == obj
+
+
+def test_dataclass_omit_ignored() -> None:
+    obj = Point(1)
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_STRUCT_AS_ARRAY
+        | ormsgpack.OPT_OMIT_NONE
+        | ormsgpack.OPT_OMIT_DEFAULTS,
+    )
+    assert packed == msgpack.packb([1, 0, None])
+    assert ormsgpack.unpackb(packed, type=Point) == obj
+
+
+def test_dataclass_non_init_field() -> None:
+    obj = Derived(1)
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([1, 2])
+    assert ormsgpack.unpackb(packed, type=Derived) == obj
+
+
+def test_dataclass_slots() -> None:
+    obj = Slotted(1, "b")
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([1, "b"])
+    assert ormsgpack.unpackb(packed, type=Slotted) == obj
+
+
+def test_dataclass_nested() -> None:
+    @dataclasses.dataclass
+    class Line:
+        start: Point
+        end: Point
+
+    obj = Line(Point(0), Point(1, 1))
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([[0, 0, None], [1, 1, None]])
+    assert ormsgpack.unpackb(packed, type=Line) == obj
+
+
+def test_dataclass_nested_optional() -> None:
+    @dataclasses.dataclass
+    class Node:
+        point: Optional[Point]
+        slotted: "Slotted | None"
+        points: list[Point]
+
+    obj = Node(Point(1), Slotted(2, "b"), [Point(3)])
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([[1, 0, None], [2, "b"], [[3, 0, None]]])
+    assert ormsgpack.unpackb(packed, type=Node) == Node(
+        Point(1), Slotted(2, "b"), [[3, 0, None]]  # type: ignore[list-item]
+    )
+    obj = Node(None, None, [])
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert ormsgpack.unpackb(packed, type=Node) == obj
+
+
+def test_dataclass_nested_unresolved_annotation() -> None:
+    @dataclasses.dataclass
+    class Line:
+        start: "Undefined"  # type: ignore[name-defined]  # noqa: F821
+        end: Point
+
+    packed = msgpack.packb([[0, 0, None], [1, 1, None]])
+    assert ormsgpack.unpackb(packed, type=Line) == Line([0, 0, None], Point(1, 1))
+
+
+def test_dataclass_nested_forward_reference() -> None:
+    @dataclasses.dataclass
+    class Line:
+        start: "Later"  # type: ignore[name-defined]  # noqa: F821
+        end: Optional["Later"]  # type: ignore[name-defined]  # noqa: F821
+
+    packed = msgpack.packb([[0, 0, None], [1, 1, None]])
+    assert ormsgpack.unpackb(packed, type=Line) == Line([0, 0, None], [1, 1, None])
+    globals()["Later"] = Point
+    try:
+        assert ormsgpack.unpackb(packed, type=Line) == Line(Point(0), Point(1, 1))
+    finally:
+        del globals()["Later"]
+
+
+def test_dataclass_nested_length_mismatch() -> None:
+    @dataclasses.dataclass
+    class Line:
+        start: Point
+        end: Point
+
+    with pytest.raises(ormsgpack.MsgpackDecodeError) as exc_info:
+        ormsgpack.unpackb(msgpack.packb([[0, 0, None], [1]]), type=Line)
+    assert (
+        str(exc_info.value)
+        == "The array does not match the fields of the type argument"
+    )
+
+
+def test_dataclass_empty() -> None:
+    @dataclasses.dataclass
+    class Empty:
+        pass
+
+    packed = ormsgpack.packb(Empty(), option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([])
+    assert ormsgpack.unpackb(packed, type=Empty) == Empty()
+
+
+def test_dataclass_length_mismatch() -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError) as exc_info:
+        ormsgpack.unpackb(msgpack.packb([1, 2]), type=Point)
+    assert (
+        str(exc_info.value)
+        == "The array does not match the fields of the type argument"
+    )
+
+
+def test_pydantic_model() -> None:
+    obj = Model(a=1, B="b")
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_STRUCT_AS_ARRAY,
+    )
+    assert packed == msgpack.packb([1, "b"])
+    assert ormsgpack.unpackb(packed, type=Model) == obj
+
+
+def test_pydantic_model_nested() -> None:
+    class Outer(pydantic.BaseModel):
+        inner: Model
+        point: Optional[Point] = None
+
+    obj = Outer(inner=Model(a=1, B="b"), point=Point(2))
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_STRUCT_AS_ARRAY,
+    )
+    assert packed == msgpack.packb([[1, "b"], [2, 0, None]])
+    assert ormsgpack.unpackb(packed, type=Outer) == obj
+
+
+def test_pydantic_model_with_extra() -> None:
+    class ExtraModel(pydantic.BaseModel):
+        model_config = pydantic.ConfigDict(extra="allow")
+
+        a: int
+
+    obj = ExtraModel(a=1, b=2)  # type: ignore[call-arg]
+    packed = ormsgpack.packb(
+        obj,
+        option=ormsgpack.OPT_SERIALIZE_PYDANTIC | ormsgpack.OPT_STRUCT_AS_ARRAY,
+    )
+    assert packed == msgpack.packb([1])
+
+
+def test_attrs() -> None:
+    attrs = pytest.importorskip("attrs")
+
+    @attrs.define
+    class Attrs:
+        a: int
+        b: str = attrs.field(kw_only=True)
+        _c: int = 0
+        d: int = attrs.field(init=False, default=4)
+
+    obj = Attrs(1, b="b", c=3)
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([1, "b", 4])
+    assert ormsgpack.unpackb(packed, type=Attrs) == Attrs(1, b="b")
+
+
+def test_attrs_nested() -> None:
+    attrs = pytest.importorskip("attrs")
+
+    @attrs.define
+    class Line:
+        start: Point
+        end: Optional[Point] = None
+
+    obj = Line(Point(0), Point(1, 1))
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_STRUCT_AS_ARRAY)
+    assert packed == msgpack.packb([[0, 0, None], [1, 1, None]])
+    assert ormsgpack.unpackb(packed, type=Line) == obj
+
+
+def test_map_unchanged() -> None:
+    obj = Point(1, 2, "p")
+    packed = ormsgpack.packb(obj)
+    assert packed == msgpack.packb({"x": 1, "y": 2, "l": "p"})
@@ -0,0 +1,128 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+import struct
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+TIMEDELTA_PARAMS = (
+    pytest.param(datetime.timedelta(), "PT0S", id="zero"),
+    pytest.param(datetime.timedelta(days=1), "P1D", id="days"),
+    pytest.param(
+        datetime.timedelta(days=1, hours=2, minutes=3, seconds=4, microseconds=500000),
+        "P1DT2H3M4.5S",
+        id="all",
+    ),
+    pytest.param(datetime.timedelta(hours=2), "PT2H", id="hours"),
+    pytest.param(datetime.timedelta(minutes=3), "PT3M", id="minutes"),
+    pytest.param(datetime.timedelta(microseconds=1), "PT0.000001S", id="microseconds"),
+    pytest.param(datetime.timedelta(seconds=-1), "-PT1S", id="negative"),
+    pytest.param(
+        datetime.timedelta(days=-1, microseconds=-10),
+        "-P1DT0.00001S",
+        id="negative days",
+    ),
+    pytest.param(datetime.timedelta.max, "P999999999DT23H59M59.999999S", id="max"),
+    pytest.param(datetime.timedelta.min, "-P999999999D", id="min"),
+)
+
+
+def test_timedelta_not_serialized() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(datetime.timedelta())
+
+
+@pytest.mark.parametrize("value, expected", TIMEDELTA_PARAMS)
+def test_timedelta_iso8601(value: datetime.timedelta, expected: str) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_SERIALIZE_TIMEDELTA)
+    assert packed == msgpack.packb(expected)
+
+
+@pytest.mark.parametrize("value, expected", TIMEDELTA_PARAMS)
+def test_timedelta_as_seconds(value: datetime.timedelta, expected: str) -> None:
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_TIMEDELTA_AS_SECONDS)
+    assert packed == msgpack.packb(value.total_seconds())
+
+
+@pytest.mark.parametrize("value, expected", TIMEDELTA_PARAMS)
+def test_timedelta_as_microseconds(value: datetime.timedelta, expected: str) -> None:
+    microseconds = value // datetime.timedelta(microseconds=1)
+    if -(2**63) <= microseconds < 2**64:
+        packed = ormsgpack.packb(value, option=ormsgpack.OPT_TIMEDELTA_AS_MICROSECONDS)
+        assert packed == msgpack.packb(microseconds)
+    else:
+        with pytest.raises(ormsgpack.MsgpackEncodeError):
+            ormsgpack.packb(value, option=ormsgpack.OPT_TIMEDELTA_AS_MICROSECONDS)
+
+
+@pytest.mark.parametrize("value, expected", TIMEDELTA_PARAMS)
+def test_timedelta_as_ext(value: datetime.timedelta, expected: str) -> None:
+    seconds = value.days * 86400 + value.seconds
+    nanoseconds = value.microseconds * 1000
+    if seconds >> 34 == 0:
+        data = struct.pack(">Q", nanoseconds << 34 | seconds)
+        if nanoseconds == 0 and seconds >> 32 == 0:
+            data = data[4:]
+    else:
+        data = struct.pack(">Iq", nanoseconds, seconds)
+
+    packed = ormsgpack.packb(value, option=ormsgpack.OPT_TIMEDELTA_AS_EXT)
+    assert packed == msgpack.packb(msgpack.ExtType(2, data))
+    unpacked = ormsgpack.unpackb(packed, option=ormsgpack.OPT_TIMEDELTA_AS_EXT)
+    assert unpacked == value
+
+    packed = ormsgpack.packb(
+        [value],
+        option=ormsgpack.OPT_TIMEDELTA_AS_EXT | ormsgpack.OPT_SERIALIZE_TIMEDELTA,
+        timedelta_tag=42,
+    )
+    assert packed == msgpack.packb([msgpack.ExtType(42, data)])
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_TIMEDELTA_AS_EXT,
+        timedelta_tag=42,
+    )
+    assert unpacked == [value]
+
+    packed = msgpack.packb({msgpack.ExtType(2, data): True})
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_TIMEDELTA_AS_EXT | ormsgpack.OPT_NON_STR_KEYS,
+    )
+    assert unpacked == {value: True}
+
+
+@pytest.mark.parametrize(
+    "data",
+    (
+        pytest.param(b"\x00\x00", id="invalid length"),
+        pytest.param(struct.pack(">Iq", 1_000_000_000, 0), id="invalid nanoseconds"),
+        pytest.param(struct.pack(">Iq", 0, 10**9 * 86400), id="out of range"),
+    ),
+)
+def test_timedelta_ext_invalid(data: bytes) -> None:
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(
+            msgpack.packb(msgpack.ExtType(2, data)),
+            option=ormsgpack.OPT_TIMEDELTA_AS_EXT,
+        )
+
+
+def test_timedelta_options_invalid() -> None:
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(
+            datetime.timedelta(),
+            option=ormsgpack.OPT_TIMEDELTA_AS_SECONDS
+            | ormsgpack.OPT_TIMEDELTA_AS_MICROSECONDS,
+        )
+
+
+def test_timedelta_subclass() -> None:
+    class ATimedelta(datetime.timedelta):
+        pass
+
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(ATimedelta(), option=ormsgpack.OPT_SERIALIZE_TIMEDELTA)
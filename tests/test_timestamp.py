@@ -0,0 +1,227 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+import struct
+
+import msgpack
+import pytest
+
+import ormsgpack
+
+TIMESTAMP_PARAMS = (
+    pytest.param(0, 0, id="epoch"),
+    pytest.param(2**32 - 1, 0, id="32-bit"),
+    pytest.param(2**34 - 1, 999_999_999, id="64-bit"),
+    pytest.param(2**34, 0, id="96-bit"),
+    pytest.param(-1, 999_999_999, id="negative"),
+    pytest.param(2**63 - 1, 999_999_999, id="max"),
+    pytest.param(-(2**63), 0, id="min"),
+)
+
+
+def pack_timestamp(seconds: int, nanoseconds: int) -> bytes:
+    if seconds >> 34 == 0:
+        value = nanoseconds << 34 | seconds
+        if value >> 32 == 0:
+            data = struct.pack(">I", value)
+        else:
+            data = struct.pack(">Q", value)
+    else:
+        data = struct.pack(">Iq", nanoseconds, seconds)
+    return msgpack.packb(msgpack.ExtType(-1, data))
+
+
+@pytest.mark.parametrize("seconds, nanoseconds", TIMESTAMP_PARAMS)
+def test_timestamp(seconds: int, nanoseconds: int) -> None:
+    value = ormsgpack.Timestamp(seconds, nanoseconds)
+    assert value.seconds == seconds
+    assert value.nanoseconds == nanoseconds
+
+    packed = ormsgpack.packb(value)
+    assert packed == pack_timestamp(seconds, nanoseconds)
+    unpacked = ormsgpack.unpackb(
+        packed, option=ormsgpack.OPT_TIMESTAMP_EXT_AS_TIMESTAMP
+    )
+    assert isinstance(unpacked, ormsgpack.Timestamp)
+    assert unpacked == value
+    assert ormsgpack.packb(unpacked) == packed
+
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_TIMESTAMP_EXT_AS_TIMESTAMP
+        | ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT,
+    )
+    assert unpacked == value
+
+
+@pytest.mark.parametrize("seconds, nanoseconds", TIMESTAMP_PARAMS)
+def test_timestamp_map_key(seconds: int, nanoseconds: int) -> None:
+    obj = {ormsgpack.Timestamp(seconds, nanoseconds): True}
+    with pytest.raises(ormsgpack.MsgpackEncodeError):
+        ormsgpack.packb(obj)
+    packed = ormsgpack.packb(obj, option=ormsgpack.OPT_NON_STR_KEYS)
+    assert packed == b"\x81" + pack_timestamp(seconds, nanoseconds) + b"\xc3"
+    unpacked = ormsgpack.unpackb(
+        packed,
+        option=ormsgpack.OPT_NON_STR_KEYS | ormsgpack.OPT_TIMESTAMP_EXT_AS_TIMESTAMP,
+    )
+    assert unpacked == obj
+
+
+def test_timestamp_default() -> None:
+    assert ormsgpack.Timestamp(1) == ormsgpack.Timestamp(1, 0)
+    assert ormsgpack.Timestamp(seconds=1, nanoseconds=2) == ormsgpack.Timestamp(1, 2)
+
+
+@pytest.mark.parametrize(
+    "args",
+    (
+        pytest.param((), id="missing seconds"),
+        pytest.param((1.0,), id="float"),
+        pytest.param((1, 2, 3), id="too many"),
+    ),
+)
+def test_timestamp_invalid_type(args: tuple[object, ...]) -> None:
+    with pytest.raises(TypeError):
+        ormsgpack.Timestamp(*args)  # type: ignore[arg-type]
+
+
+@pytest.mark.parametrize(
+    "seconds, nanoseconds",
+    (
+        pytest.param(0, -1, id="negative nanoseconds"),
+        pytest.param(0, 1_000_000_000, id="nanoseconds"),
+    ),
+)
+def test_timestamp_invalid_value(seconds: int, nanoseconds: int) -> None:
+    with pytest.raises(ValueError):
+        ormsgpack.Timestamp(seconds, nanoseconds)
+
+
+def test_timestamp_overflow() -> None:
+    with pytest.raises(OverflowError):
+        ormsgpack.Timestamp(2**63)
+
+
+def test_timestamp_compare() -> None:
+    values = [
+        ormsgpack.Timestamp(-1, 999_999_999),
+        ormsgpack.Timestamp(0),
+        ormsgpack.Timestamp(0, 1),
+        ormsgpack.Timestamp(1),
+    ]
+    assert sorted(reversed(values)) == values
+    assert values[0] < values[1] <= values[1] < values[2]
+    assert values[3] > values[2] >= values[2] > values[1]
+    assert values[1] != values[2]
+    assert values[1] != (0, 0)
+    assert len({ormsgpack.Timestamp(0), ormsgpack.Timestamp(0, 0)}) == 1
+    with pytest.raises(TypeError):
+        assert values[0] < 0  # type: ignore[operator]
+
+
+def test_timestamp_repr() -> None:
+    assert (
+        repr(ormsgpack.Timestamp(-1, 5)) == "Timestamp(seconds=-1, nanoseconds=5)"
+    )
+
+
+def test_timestamp_immutable() -> None:
+    value = ormsgpack.Timestamp(1)
+    with pytest.raises(AttributeError):
+        value.seconds = 2  # type: ignore[misc]
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        pytest.param(
+            ormsgpack.Timestamp(0),
+            datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc),
+            id="epoch",
+        ),
+        pytest.param(
+            ormsgpack.Timestamp(-1, 999_999_999),
+            datetime.datetime(
+                1969, 12, 31, 23, 59, 59, 999_999, tzinfo=datetime.timezone.utc
+            ),
+            id="truncated",
+        ),
+        pytest.param(
+            ormsgpack.Timestamp(253402300799, 999_999_000),
+            datetime.datetime.max.replace(tzinfo=datetime.timezone.utc),
+            id="max",
+        ),
+    ),
+)
+def test_timestamp_to_datetime(
+    value: ormsgpack.Timestamp, expected: datetime.datetime
+) -> None:
+    assert value.to_datetime() == expected
+    assert value.to_datetime().tzinfo is datetime.timezone.utc
+
+
+@pytest.mark.parametrize("seconds", (253402300800, 2**63 - 1, -(2**63)))
+def test_timestamp_to_datetime_out_of_range(seconds: int) -> None:
+    with pytest.raises(ValueError):
+        ormsgpack.Timestamp(seconds).to_datetime()
+
+    packed = ormsgpack.packb(ormsgpack.Timestamp(seconds))
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(packed, option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT)
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    (
+        pytest.param(
+            datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc),
+            ormsgpack.Timestamp(0),
+            id="epoch",
+        ),
+        pytest.param(
+            datetime.datetime(
+                1969, 12, 31, 23, 59, 59, 500_000, tzinfo=datetime.timezone.utc
+            ),
+            ormsgpack.Timestamp(-1, 500_000_000),
+            id="negative",
+        ),
+        pytest.param(
+            datetime.datetime(
+                1970,
+                1,
+                1,
+                2,
+                microsecond=1,
+                tzinfo=datetime.timezone(datetime.timedelta(hours=2)),
+            ),
+            ormsgpack.Timestamp(0, 1000),
+            id="offset",
+        ),
+    ),
+)
+def test_timestamp_from_datetime(
+    value: datetime.datetime, expected: ormsgpack.Timestamp
+) -> None:
+    assert ormsgpack.Timestamp.from_datetime(value) == expected
+    assert ormsgpack.Timestamp.from_datetime(value).to_datetime() == value
+    assert ormsgpack.packb(
+        ormsgpack.Timestamp.from_datetime(value)
+    ) == ormsgpack.packb(value, option=ormsgpack.OPT_DATETIME_AS_TIMESTAMP_EXT)
+
+
+def test_timestamp_from_datetime_invalid() -> None:
+    with pytest.raises(ValueError):
+        ormsgpack.Timestamp.from_datetime(datetime.datetime(1970, 1, 1))
+    with pytest.raises(TypeError):
+        ormsgpack.Timestamp.from_datetime(
+            datetime.date(1970, 1, 1)  # type: ignore[arg-type]
+        )
+
+
+def test_timestamp_ext_invalid() -> None:
+    packed = msgpack.packb(
+        msgpack.ExtType(-1, struct.pack(">Iq", 1_000_000_000, 0))
+    )
+    with pytest.raises(ormsgpack.MsgpackDecodeError):
+        ormsgpack.unpackb(packed, option=ormsgpack.OPT_TIMESTAMP_EXT_AS_TIMESTAMP)